@@ -0,0 +1,106 @@
+use proc_macro2::{TokenStream, TokenTree};
+use syn::{Attribute, Ident, LitStr, Meta, Path, Result};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+
+/// The `#[partial(...)]` attributes of a struct.
+#[derive(Default)]
+pub struct StructAttributes {
+    pub name: Option<Ident>,
+    pub derives: Vec<Path>,
+}
+
+impl StructAttributes {
+    pub fn parse(attributes: &[Attribute]) -> Result<Self> {
+        let mut parsed = Self::default();
+
+        for attribute in attributes.iter().filter(|attribute| attribute.path().is_ident("partial")) {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    parsed.name = Some(name.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("derive") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let derives: Punctuated<Path, Comma> = content.parse_terminated(Path::parse_mod_style, Comma)?;
+                    parsed.derives.extend(derives);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `name` or `derive`"))
+                }
+            })?;
+        }
+
+        Ok(parsed)
+    }
+
+    /// Returns whether the partial struct derives either of the serde traits, so `#[serde]` attributes are valid on it.
+    pub fn derives_serde(&self) -> bool {
+        self.derives.iter().any(|path| {
+            path.segments.last().map_or(false, |segment| segment.ident == "Serialize" || segment.ident == "Deserialize")
+        })
+    }
+}
+
+/// How a field of a struct is represented in its partial struct.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// The field must be present, its partial field is an `Option`.
+    Required,
+    /// The field uses `Default::default()` when missing, its partial field is an `Option`.
+    Default,
+    /// The field is already an `Option` and is used as is.
+    Optional,
+    /// The field is itself a partial configuration.
+    Nested,
+}
+
+/// Gets the kind of a field from its `#[partial(...)]` attributes, falling back to `Optional` for `Option` fields.
+pub fn field_kind(attributes: &[Attribute], is_option: bool) -> Result<FieldKind> {
+    let mut kind = if is_option { FieldKind::Optional } else { FieldKind::Required };
+
+    for attribute in attributes.iter().filter(|attribute| attribute.path().is_ident("partial")) {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("nested") {
+                kind = FieldKind::Nested;
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                kind = FieldKind::Default;
+                Ok(())
+            } else {
+                Err(meta.error("expected `nested` or `default`"))
+            }
+        })?;
+    }
+
+    Ok(kind)
+}
+
+fn serde_options(attribute: &Attribute) -> Vec<String> {
+    match attribute.meta {
+        Meta::List(ref list) => {
+            list.tokens
+                .clone()
+                .into_iter()
+                .filter_map(|token| match token {
+                    TokenTree::Ident(ident) => Some(ident.to_string()),
+                    _ => None,
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Gets the `#[serde]` attributes which remain valid when a value becomes optional, those which provide
+/// defaults or custom functions for the value are dropped as they expect the original type.
+pub fn serde_attributes(attributes: &[Attribute]) -> Vec<TokenStream> {
+    const UNSUPPORTED: &[&str] = &["default", "with", "serialize_with", "deserialize_with"];
+
+    attributes.iter()
+        .filter(|attribute| attribute.path().is_ident("serde"))
+        .filter(|attribute| !serde_options(attribute).iter().any(|option| UNSUPPORTED.contains(&option.as_str())))
+        .map(|attribute| quote! { #attribute })
+        .collect()
+}
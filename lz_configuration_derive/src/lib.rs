@@ -0,0 +1,42 @@
+//! Derive macros for the `Merge` and `PartialConfiguration` traits of `lz_configuration`.
+//!
+//! Enable the `derive` feature of `lz_configuration` to use these through `lz_configuration::merge`.
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+mod attributes;
+mod merge;
+mod partial_configuration;
+
+use proc_macro::TokenStream;
+use syn::DeriveInput;
+
+/// Derives `Merge` by merging every field of a struct, or by replacing the whole value of an enum.
+#[proc_macro_derive(Merge)]
+pub fn derive_merge(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    merge::derive(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derives a `Partial` mirror of a configuration struct where every field is optional, implementing
+/// `PartialConfiguration`, `Merge`, `Default` and `From` the configuration.
+///
+/// The struct may be annotated with `#[partial(name = "...")]` to name the partial struct and
+/// `#[partial(derive(...))]` to derive traits for it, `#[serde]` attributes are copied when `Serialize` or
+/// `Deserialize` are derived. Fields may be annotated with `#[partial(nested)]` when their type also derives
+/// `PartialConfiguration` or `#[partial(default)]` when a missing value should use `Default::default()`.
+#[proc_macro_derive(PartialConfiguration, attributes(partial, serde))]
+pub fn derive_partial_configuration(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    partial_configuration::derive(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
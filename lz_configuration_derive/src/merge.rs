@@ -0,0 +1,47 @@
+use proc_macro2::TokenStream;
+use syn::{Data, DeriveInput, Error, Fields, Index, Result};
+
+pub fn derive(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match input.data {
+        Data::Struct(ref data) => {
+            let merges: Vec<TokenStream> = match data.fields {
+                Fields::Named(ref fields) => {
+                    fields.named
+                        .iter()
+                        .map(|field| {
+                            let field = &field.ident;
+                            quote! { ::lz_configuration::merge::Merge::merge(&mut self.#field, other.#field); }
+                        })
+                        .collect()
+                }
+                Fields::Unnamed(ref fields) => {
+                    (0..fields.unnamed.len())
+                        .map(|index| {
+                            let index = Index::from(index);
+                            quote! { ::lz_configuration::merge::Merge::merge(&mut self.#index, other.#index); }
+                        })
+                        .collect()
+                }
+                Fields::Unit => Vec::new(),
+            };
+
+            quote! {
+                let _ = &other;
+                #(#merges)*
+            }
+        }
+        Data::Enum(_) => quote! { *self = other; },
+        Data::Union(_) => return Err(Error::new_spanned(name, "Merge cannot be derived for unions")),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::lz_configuration::merge::Merge for #name #type_generics #where_clause {
+            fn merge(&mut self, other: Self) {
+                #body
+            }
+        }
+    })
+}
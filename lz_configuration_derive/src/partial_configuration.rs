@@ -0,0 +1,225 @@
+use attributes::{self, FieldKind, StructAttributes};
+use proc_macro2::TokenStream;
+use syn::{Data, DeriveInput, Error, Field, Fields, Ident, PathArguments, Result, Type};
+
+struct PartialField<'a> {
+    field: &'a Field,
+    name: &'a Ident,
+    kind: FieldKind,
+    partial_type: TokenStream,
+}
+
+fn is_option(ty: &Type) -> bool {
+    match *ty {
+        Type::Path(ref path) if path.qself.is_none() => {
+            path.path.segments.last().map_or(false, |segment| {
+                segment.ident == "Option" &&
+                match segment.arguments {
+                    PathArguments::AngleBracketed(_) => true,
+                    _ => false,
+                }
+            })
+        }
+        _ => false,
+    }
+}
+
+/// Gets the partial type of a nested configuration, which is named after the type prefixed with `Partial`.
+fn nested_type(field: &Field) -> Result<Type> {
+    let mut ty = field.ty.clone();
+    match ty {
+        Type::Path(ref mut path) if path.qself.is_none() => {
+            if let Some(segment) = path.path.segments.last_mut() {
+                segment.ident = format_ident!("Partial{}", segment.ident);
+                return Ok(ty.clone());
+            }
+        }
+        _ => {}
+    }
+
+    Err(Error::new_spanned(&field.ty, "a nested field must be a named type which derives PartialConfiguration"))
+}
+
+fn partial_field(field: &Field) -> Result<PartialField<'_>> {
+    let kind = attributes::field_kind(&field.attrs, is_option(&field.ty))?;
+
+    let ty = &field.ty;
+    let partial_type = match kind {
+        FieldKind::Required | FieldKind::Default => quote! { ::std::option::Option<#ty> },
+        FieldKind::Optional => quote! { #ty },
+        FieldKind::Nested => {
+            let nested = nested_type(field)?;
+            quote! { #nested }
+        }
+    };
+
+    Ok(PartialField {
+        field: field,
+        name: field.ident.as_ref().expect("named field"),
+        kind: kind,
+        partial_type: partial_type,
+    })
+}
+
+pub fn derive(input: &DeriveInput) -> Result<TokenStream> {
+    let fields = match input.data {
+        Data::Struct(ref data) => {
+            match data.fields {
+                Fields::Named(ref fields) => fields.named.iter().map(partial_field).collect::<Result<Vec<_>>>()?,
+                _ => return Err(Error::new_spanned(&input.ident, "PartialConfiguration can only be derived for structs with named fields")),
+            }
+        }
+        _ => return Err(Error::new_spanned(&input.ident, "PartialConfiguration can only be derived for structs")),
+    };
+
+    let attributes = StructAttributes::parse(&input.attrs)?;
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let partial_name = attributes.name.clone().unwrap_or_else(|| format_ident!("Partial{}", name));
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let generics = &input.generics;
+
+    let doc = format!("A partial `{}` where every field is optional, see `PartialConfiguration`.", name);
+
+    let derives = if attributes.derives.is_empty() {
+        TokenStream::new()
+    } else {
+        let derives = &attributes.derives;
+        quote! { #[derive(#(#derives),*)] }
+    };
+    let struct_serde = if attributes.derives_serde() {
+        let copied = attributes::serde_attributes(&input.attrs);
+        quote! {
+            #(#copied)*
+            #[serde(default)]
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let declarations = fields.iter().map(|field| {
+        let field_vis = &field.field.vis;
+        let field_name = field.name;
+        let partial_type = &field.partial_type;
+        let serde = if attributes.derives_serde() { attributes::serde_attributes(&field.field.attrs) } else { Vec::new() };
+
+        quote! {
+            #(#serde)*
+            #field_vis #field_name: #partial_type
+        }
+    });
+
+    let defaults = fields.iter().map(|field| {
+        let field_name = field.name;
+        quote! { #field_name: ::std::default::Default::default() }
+    });
+
+    let merges = fields.iter().map(|field| {
+        let field_name = field.name;
+        match field.kind {
+            FieldKind::Nested => quote! { ::lz_configuration::merge::Merge::merge(&mut self.#field_name, other.#field_name); },
+            _ => {
+                quote! {
+                    if other.#field_name.is_some() {
+                        self.#field_name = other.#field_name;
+                    }
+                }
+            }
+        }
+    });
+
+    let conversions = fields.iter().map(|field| {
+        let field_name = field.name;
+        match field.kind {
+            FieldKind::Required | FieldKind::Default => quote! { #field_name: ::std::option::Option::Some(configuration.#field_name) },
+            FieldKind::Optional => quote! { #field_name: configuration.#field_name },
+            FieldKind::Nested => quote! { #field_name: ::std::convert::From::from(configuration.#field_name) },
+        }
+    });
+
+    let checks = fields.iter().map(|field| {
+        let field_name = field.name;
+        let key = field_name.to_string();
+        match field.kind {
+            FieldKind::Required => {
+                quote! {
+                    if self.#field_name.is_none() {
+                        missing.push(::std::string::String::from(#key));
+                    }
+                }
+            }
+            FieldKind::Nested => {
+                quote! {
+                    let #field_name = match ::lz_configuration::merge::PartialConfiguration::finalize(self.#field_name) {
+                        ::std::result::Result::Ok(value) => ::std::option::Option::Some(value),
+                        ::std::result::Result::Err(err) => {
+                            missing.extend(err.within(#key));
+                            ::std::option::Option::None
+                        }
+                    };
+                }
+            }
+            _ => TokenStream::new(),
+        }
+    });
+
+    let finalized = fields.iter().map(|field| {
+        let field_name = field.name;
+        match field.kind {
+            FieldKind::Required => quote! { #field_name: self.#field_name.unwrap() },
+            FieldKind::Default => quote! { #field_name: self.#field_name.unwrap_or_default() },
+            FieldKind::Optional => quote! { #field_name: self.#field_name },
+            FieldKind::Nested => quote! { #field_name: #field_name.unwrap() },
+        }
+    });
+
+    Ok(quote! {
+        #[doc = #doc]
+        #derives
+        #struct_serde
+        #vis struct #partial_name #generics #where_clause {
+            #(#declarations,)*
+        }
+
+        impl #impl_generics ::std::default::Default for #partial_name #type_generics #where_clause {
+            fn default() -> Self {
+                #partial_name {
+                    #(#defaults,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::lz_configuration::merge::Merge for #partial_name #type_generics #where_clause {
+            fn merge(&mut self, other: Self) {
+                #(#merges)*
+            }
+        }
+
+        impl #impl_generics ::std::convert::From<#name #type_generics> for #partial_name #type_generics #where_clause {
+            fn from(configuration: #name #type_generics) -> Self {
+                #partial_name {
+                    #(#conversions,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::lz_configuration::merge::PartialConfiguration for #partial_name #type_generics #where_clause {
+            type Configuration = #name #type_generics;
+
+            fn finalize(self) -> ::std::result::Result<Self::Configuration, ::lz_configuration::merge::MissingFieldsError> {
+                #[allow(unused_mut)]
+                let mut missing = ::std::vec::Vec::new();
+                #(#checks)*
+
+                if !missing.is_empty() {
+                    return ::std::result::Result::Err(::lz_configuration::merge::MissingFieldsError::new(missing));
+                }
+
+                ::std::result::Result::Ok(#name {
+                    #(#finalized,)*
+                })
+            }
+        }
+    })
+}
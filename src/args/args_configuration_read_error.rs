@@ -0,0 +1,17 @@
+quick_error! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ArgsConfigurationReadError {
+        Malformed(argument: String, reason: String) {
+            description("malformed argument")
+            display("malformed argument '{}': {}", argument, reason)
+        }
+        Missing(argument: String) {
+            description("missing argument")
+            display("missing argument {}", argument)
+        }
+        Invalid(argument: String, message: String) {
+            description("invalid argument")
+            display("invalid argument {}: {}", argument, message)
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use ConfigurationReader;
+use super::{ArgsConfigurationReadError, ArgsKeySyntax};
+use value::{ConfigurationValue, ConfigurationValueError};
+use futures::IntoFuture;
+use futures::future::FutureResult;
+use serde::de::DeserializeOwned;
+use std::env;
+use std::marker::PhantomData;
+
+/// A `ConfigurationReader` which reads a configuration from command-line arguments.
+///
+/// Each argument names a key using an `ArgsKeySyntax`, the value is either assigned within the argument
+/// (`--server.port=8080`) or is the following argument (`--server.port 8080`). A key without a value
+/// (`--verbose`) is given the value `true`, and a key which is repeated (`--include a --include b`)
+/// produces an array.
+#[derive(Debug)]
+pub struct ArgsConfigurationReader<C = ConfigurationValue> {
+    args: Vec<String>,
+    syntax: ArgsKeySyntax,
+    phantom_data: PhantomData<C>,
+}
+
+impl<C> ArgsConfigurationReader<C>
+    where C: DeserializeOwned
+{
+    /// Creates a new `ArgsConfigurationReader<C>` for the specified arguments, these should not include the program name.
+    pub fn new<I, S>(args: I) -> Self
+        where I: IntoIterator<Item = S>,
+              S: Into<String>
+    {
+        Self {
+            args: args.into_iter().map(Into::into).collect(),
+            syntax: ArgsKeySyntax::default(),
+            phantom_data: Default::default(),
+        }
+    }
+
+    /// Creates a new `ArgsConfigurationReader<C>` for the arguments the process was started with.
+    pub fn from_env() -> Self {
+        Self::new(env::args().skip(1))
+    }
+
+    /// Uses the specified `ArgsKeySyntax` to parse the arguments.
+    pub fn with_syntax(mut self, syntax: ArgsKeySyntax) -> Self {
+        self.syntax = syntax;
+        self
+    }
+}
+
+impl<C> ArgsConfigurationReader<C> {
+    pub fn syntax(&self) -> &ArgsKeySyntax {
+        &self.syntax
+    }
+
+    fn to_value(&self) -> Result<ConfigurationValue, ArgsConfigurationReadError> {
+        let mut configuration = ConfigurationValue::table();
+
+        let mut args = self.args.iter().peekable();
+        while let Some(argument) = args.next() {
+            let (path, value) = match self.syntax.split(argument) {
+                Some(split) => split,
+                None if self.syntax.is_key(argument) => {
+                    return Err(ArgsConfigurationReadError::Malformed(argument.clone(), "invalid key".to_owned()));
+                }
+                None => {
+                    return Err(ArgsConfigurationReadError::Malformed(argument.clone(), "value without a key".to_owned()));
+                }
+            };
+
+            let value = match value {
+                Some(value) => value.to_owned(),
+                None => {
+                    let next_is_value = args.peek().map_or(false, |next| !self.syntax.is_key(next));
+                    if next_is_value {
+                        args.next().unwrap().clone()
+                    } else {
+                        "true".to_owned()
+                    }
+                }
+            };
+
+            configuration.append(&path, ConfigurationValue::String(value));
+        }
+
+        Ok(configuration)
+    }
+
+    fn read_error(&self, error: ConfigurationValueError) -> ArgsConfigurationReadError {
+        let argument = self.syntax.argument_name(error.path());
+        match error.message() {
+            Some(message) => ArgsConfigurationReadError::Invalid(argument, message.to_owned()),
+            None => ArgsConfigurationReadError::Missing(argument),
+        }
+    }
+}
+
+impl<C> Clone for ArgsConfigurationReader<C> {
+    fn clone(&self) -> Self {
+        Self {
+            args: self.args.clone(),
+            syntax: self.syntax.clone(),
+            phantom_data: Default::default(),
+        }
+    }
+}
+
+impl<C> ConfigurationReader for ArgsConfigurationReader<C>
+    where C: DeserializeOwned + Send + 'static
+{
+    type Configuration = C;
+    type Error = ArgsConfigurationReadError;
+    type ReadResult = FutureResult<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        self.to_value()
+            .and_then(|value| value.deserialize_into().map_err(|err| self.read_error(err)))
+            .into_future()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ConfigurationReader;
+    use args::{ArgsConfigurationReader, ArgsConfigurationReadError, ArgsKeySyntax};
+    use value::ConfigurationValue;
+    use futures::Future;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct ServerConfiguration {
+        port: u16,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct TestConfiguration {
+        server: ServerConfiguration,
+        verbose: bool,
+        include: Vec<String>,
+    }
+
+    #[test]
+    fn read_configuration_parses_arguments() {
+        // Arrange
+        let args = vec!["--server.port=8080", "--verbose", "--include", "a", "--include", "b"];
+        let reader = ArgsConfigurationReader::<TestConfiguration>::new(args);
+
+        // Act
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration,
+                   TestConfiguration {
+                       server: ServerConfiguration { port: 8080 },
+                       verbose: true,
+                       include: vec!["a".to_owned(), "b".to_owned()],
+                   });
+    }
+
+    #[test]
+    fn read_configuration_uses_key_syntax() {
+        // Arrange
+        let syntax = ArgsKeySyntax::default().with_prefix("-").with_separator("-").with_assignment(":");
+        let reader = ArgsConfigurationReader::<ConfigurationValue>::new(vec!["-server-port:8080"]).with_syntax(syntax);
+
+        // Act
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration.get(&["server", "port"]), Some(&ConfigurationValue::from("8080")));
+    }
+
+    #[test]
+    fn read_configuration_value_without_key_returns_malformed() {
+        // Arrange
+        let reader = ArgsConfigurationReader::<ConfigurationValue>::new(vec!["8080"]);
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        assert_eq!(error, ArgsConfigurationReadError::Malformed("8080".to_owned(), "value without a key".to_owned()));
+    }
+
+    #[test]
+    fn read_configuration_unparsable_value_returns_invalid() {
+        // Arrange
+        let args = vec!["--server.port=http", "--include=a"];
+        let reader = ArgsConfigurationReader::<TestConfiguration>::new(args);
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        match error {
+            ArgsConfigurationReadError::Invalid(ref argument, _) => assert_eq!(argument, "--server.port"),
+            other => panic!("expected an invalid argument error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+/// Describes how keys and values are written in command-line arguments.
+///
+/// With the default syntax an argument is written as `--server.port=8080` or `--server.port 8080`, and an
+/// argument without a value such as `--verbose` is given the value `true`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgsKeySyntax {
+    prefix: String,
+    separator: String,
+    assignment: String,
+}
+
+impl Default for ArgsKeySyntax {
+    fn default() -> Self {
+        Self {
+            prefix: "--".to_owned(),
+            separator: ".".to_owned(),
+            assignment: "=".to_owned(),
+        }
+    }
+}
+
+impl ArgsKeySyntax {
+    /// Uses the specified prefix to identify an argument which names a key.
+    pub fn with_prefix<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Uses the specified separator between nested keys.
+    pub fn with_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Uses the specified text to separate a key from its value within a single argument.
+    pub fn with_assignment<A: Into<String>>(mut self, assignment: A) -> Self {
+        self.assignment = assignment.into();
+        self
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    pub fn assignment(&self) -> &str {
+        &self.assignment
+    }
+
+    /// Returns whether the specified argument names a key.
+    pub fn is_key(&self, argument: &str) -> bool {
+        argument.len() > self.prefix.len() && argument.starts_with(&self.prefix)
+    }
+
+    /// Splits an argument which names a key into its path of nested keys and any value assigned within the argument.
+    pub fn split<'a>(&self, argument: &'a str) -> Option<(Vec<&'a str>, Option<&'a str>)> {
+        if !self.is_key(argument) {
+            return None;
+        }
+
+        let argument = &argument[self.prefix.len()..];
+        let (key, value) = match argument.find(self.assignment.as_str()) {
+            Some(index) if !self.assignment.is_empty() => {
+                (&argument[..index], Some(&argument[index + self.assignment.len()..]))
+            }
+            _ => (argument, None),
+        };
+
+        let path: Vec<_> = if self.separator.is_empty() {
+            vec![key]
+        } else {
+            key.split(self.separator.as_str()).collect()
+        };
+
+        if path.iter().any(|key| key.is_empty()) {
+            return None;
+        }
+
+        Some((path, value))
+    }
+
+    /// Gets the argument which names the specified path of nested keys.
+    pub fn argument_name<K: AsRef<str>>(&self, path: &[K]) -> String {
+        let keys: Vec<_> = path.iter().map(AsRef::as_ref).collect();
+        format!("{}{}", self.prefix, keys.join(&self.separator))
+    }
+}
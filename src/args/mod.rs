@@ -0,0 +1,8 @@
+mod args_key_syntax;
+pub use self::args_key_syntax::*;
+
+mod args_configuration_read_error;
+pub use self::args_configuration_read_error::*;
+
+mod args_configuration_reader;
+pub use self::args_configuration_reader::*;
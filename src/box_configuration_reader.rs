@@ -0,0 +1,123 @@
+use ConfigurationReader;
+use futures::{BoxFuture, Future};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::Arc;
+
+trait ErasedReader<C, E>: Send + Sync {
+    fn read(&self) -> BoxFuture<C, E>;
+}
+
+struct MappedReader<R, F> {
+    reader: R,
+    map_err: Arc<F>,
+}
+
+impl<R, F, E> ErasedReader<R::Configuration, E> for MappedReader<R, F>
+    where R: ConfigurationReader + Send + Sync,
+          F: Fn(R::Error) -> E + Send + Sync + 'static,
+          E: Send + 'static
+{
+    fn read(&self) -> BoxFuture<R::Configuration, E> {
+        let map_err = self.map_err.clone();
+        self.reader.read_configuration().map_err(move |e| (map_err)(e)).boxed()
+    }
+}
+
+/// A `ConfigurationReader` which hides the type of another reader, so readers of different types can be
+/// stored together or returned from the same function.
+///
+/// The error of the reader is converted into `E`, either with `Into` or with a function. Clones share the
+/// same reader.
+pub struct BoxConfigurationReader<C, E> {
+    reader: Arc<ErasedReader<C, E>>,
+}
+
+impl<C, E> BoxConfigurationReader<C, E>
+    where C: Send + 'static,
+          E: Send + 'static
+{
+    /// Creates a new `BoxConfigurationReader<C, E>` converting the errors of the reader with `Into`.
+    pub fn new<R>(reader: R) -> Self
+        where R: ConfigurationReader<Configuration = C> + Send + Sync + 'static,
+              R::Error: Into<E>
+    {
+        Self::with_error_map(reader, Into::into)
+    }
+
+    /// Creates a new `BoxConfigurationReader<C, E>` converting the errors of the reader with `map_err`.
+    pub fn with_error_map<R, F>(reader: R, map_err: F) -> Self
+        where R: ConfigurationReader<Configuration = C> + Send + Sync + 'static,
+              F: Fn(R::Error) -> E + Send + Sync + 'static
+    {
+        Self {
+            reader: Arc::new(MappedReader {
+                reader: reader,
+                map_err: Arc::new(map_err),
+            }),
+        }
+    }
+}
+
+impl<C, E> BoxConfigurationReader<C, E> {
+    /// Reads the configuration, unlike `read_configuration` this does not need `E` to implement `Debug`.
+    pub(crate) fn read(&self) -> BoxFuture<C, E> {
+        self.reader.read()
+    }
+}
+
+impl<C, E> Clone for BoxConfigurationReader<C, E> {
+    fn clone(&self) -> Self {
+        Self { reader: self.reader.clone() }
+    }
+}
+
+impl<C, E> Debug for BoxConfigurationReader<C, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "BoxConfigurationReader")
+    }
+}
+
+impl<C, E> ConfigurationReader for BoxConfigurationReader<C, E>
+    where C: Send + 'static,
+          E: Debug + Send + 'static
+{
+    type Configuration = C;
+    type Error = E;
+    type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        self.read()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {BoxConfigurationReader, ConfigurationReader};
+    use memory::{MemoryConfigurationAccessor, MemoryConfigurationReadError};
+    use futures::Future;
+
+    fn reader(source: &str) -> BoxConfigurationReader<u32, String> {
+        match source {
+            "memory" => BoxConfigurationReader::with_error_map(MemoryConfigurationAccessor::new(7), |e: MemoryConfigurationReadError| format!("{:?}", e)),
+            _ => BoxConfigurationReader::with_error_map(MemoryConfigurationAccessor::empty(), |e: MemoryConfigurationReadError| format!("{:?}", e)),
+        }
+    }
+
+    #[test]
+    fn read_configuration_reads_boxed_reader() {
+        // Act
+        let configuration = reader("memory").read_configuration().wait();
+
+        // Assert
+        assert_eq!(configuration, Ok(7));
+    }
+
+    #[test]
+    fn read_configuration_maps_error() {
+        // Act
+        let error = reader("empty").read_configuration().wait().unwrap_err();
+
+        // Assert
+        assert_eq!(error, "NoConfiguration");
+    }
+}
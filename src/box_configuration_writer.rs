@@ -0,0 +1,124 @@
+use ConfigurationWriter;
+use futures::{BoxFuture, Future};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::Arc;
+
+trait ErasedWriter<C, E>: Send {
+    fn write(&mut self, configuration: &C) -> BoxFuture<(), E>;
+}
+
+struct MappedWriter<W, F> {
+    writer: W,
+    map_err: Arc<F>,
+}
+
+impl<W, F, E> ErasedWriter<W::Configuration, E> for MappedWriter<W, F>
+    where W: ConfigurationWriter + Send,
+          F: Fn(W::Error) -> E + Send + Sync + 'static,
+          E: Send + 'static
+{
+    fn write(&mut self, configuration: &W::Configuration) -> BoxFuture<(), E> {
+        let map_err = self.map_err.clone();
+        self.writer.write_configuration(configuration).map_err(move |e| (map_err)(e)).boxed()
+    }
+}
+
+/// A `ConfigurationWriter` which hides the type of another writer, so writers of different types can be
+/// stored together or returned from the same function.
+///
+/// The error of the writer is converted into `E`, either with `Into` or with a function.
+pub struct BoxConfigurationWriter<C, E> {
+    writer: Box<ErasedWriter<C, E>>,
+}
+
+impl<C, E> BoxConfigurationWriter<C, E>
+    where C: Send + 'static,
+          E: Send + 'static
+{
+    /// Creates a new `BoxConfigurationWriter<C, E>` converting the errors of the writer with `Into`.
+    pub fn new<W>(writer: W) -> Self
+        where W: ConfigurationWriter<Configuration = C> + Send + 'static,
+              W::Error: Into<E>
+    {
+        Self::with_error_map(writer, Into::into)
+    }
+
+    /// Creates a new `BoxConfigurationWriter<C, E>` converting the errors of the writer with `map_err`.
+    pub fn with_error_map<W, F>(writer: W, map_err: F) -> Self
+        where W: ConfigurationWriter<Configuration = C> + Send + 'static,
+              F: Fn(W::Error) -> E + Send + Sync + 'static
+    {
+        Self {
+            writer: Box::new(MappedWriter {
+                writer: writer,
+                map_err: Arc::new(map_err),
+            }),
+        }
+    }
+}
+
+impl<C, E> BoxConfigurationWriter<C, E> {
+    /// Writes the configuration, unlike `write_configuration` this does not need `E` to implement `Debug`.
+    pub(crate) fn write(&mut self, configuration: &C) -> BoxFuture<(), E> {
+        self.writer.write(configuration)
+    }
+}
+
+impl<C, E> Debug for BoxConfigurationWriter<C, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "BoxConfigurationWriter")
+    }
+}
+
+impl<C, E> ConfigurationWriter for BoxConfigurationWriter<C, E>
+    where C: Send + 'static,
+          E: Debug + Send + 'static
+{
+    type Configuration = C;
+    type Error = E;
+    type WriteResult = BoxFuture<(), Self::Error>;
+
+    fn write_configuration(&mut self, configuration: &Self::Configuration) -> Self::WriteResult {
+        self.write(configuration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {BoxConfigurationAccessor, ConfigurationAccessor, ConfigurationReader, ConfigurationWriter};
+    use memory::{MemoryConfigurationAccessor, MemoryConfigurationReadError};
+    use futures::Future;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum TestError {
+        Missing,
+    }
+
+    impl From<!> for TestError {
+        fn from(never: !) -> Self {
+            never
+        }
+    }
+
+    impl From<MemoryConfigurationReadError> for TestError {
+        fn from(_: MemoryConfigurationReadError) -> Self {
+            TestError::Missing
+        }
+    }
+
+    #[test]
+    fn boxed_accessor_writes_and_reads_accessor() {
+        // Arrange
+        let memory = MemoryConfigurationAccessor::empty();
+        let mut accessor: BoxConfigurationAccessor<u32, TestError> = ConfigurationAccessor::boxed(memory.clone());
+
+        // Act
+        let before = accessor.read_configuration().wait();
+        accessor.write_configuration(&3).wait().unwrap();
+
+        // Assert
+        assert_eq!(before, Err(TestError::Missing));
+        assert_eq!(accessor.read_configuration().wait(), Ok(3));
+        assert_eq!(memory.read_configuration().wait(), Ok(3));
+    }
+}
@@ -1,12 +1,49 @@
 use ConfigurationWriter;
+use super::{BroadcastOutcome, BroadcastPolicy, BroadcastReport};
 use futures::Future;
-use futures::future::{Map, Join, MapErr};
+use futures::future::{self, FutureResult, Join, Join3, Map, Then};
 use either::Either;
 
+type Settled<E> = Result<(), E>;
+
+type SettleWrite<T, E> = Then<T, Result<Settled<E>, !>, fn(Settled<E>) -> Result<Settled<E>, !>>;
+
+fn settle<E>(result: Settled<E>) -> Result<Settled<E>, !> {
+    Ok(result)
+}
+
+fn decide<F, S>(settled: Result<(Settled<F>, Settled<S>, BroadcastPolicy), !>) -> Result<(), Either<F, S>> {
+    let (first, second, policy) = match settled {
+        Ok(settled) => settled,
+        Err(never) => never,
+    };
+
+    let policy = if policy.is_achievable(2) { policy } else { BroadcastPolicy::Strict };
+
+    let succeeded = [first.is_ok(), second.is_ok()].iter().filter(|&&success| success).count();
+    if policy.is_satisfied_by(succeeded, 2) {
+        return Ok(());
+    }
+
+    first.map_err(Either::Left).and_then(|()| second.map_err(Either::Right))
+}
+
+fn report<F, S>((first, second): (Settled<F>, Settled<S>)) -> BroadcastReport<Either<F, S>> {
+    BroadcastReport::new(vec![BroadcastOutcome::new("first", first.map_err(Either::Left)),
+                              BroadcastOutcome::new("second", second.map_err(Either::Right))])
+}
+
+/// A `ConfigurationWriter` which writes a configuration to two writers.
+///
+/// Both writes always run to completion, whether the write succeeded is then decided by a `BroadcastPolicy`
+/// over the two writers, by default both writers must succeed. A quorum which two writers cannot satisfy is
+/// treated as `Strict`. When the policy is not satisfied the error of the first writer which failed is
+/// returned.
 #[derive(Debug, Clone, Default)]
 pub struct BroadcastConfigurationWriter<F, S> {
     first_writer: F,
     second_writer: S,
+    policy: BroadcastPolicy,
 }
 
 impl<F, S> BroadcastConfigurationWriter<F, S>
@@ -17,11 +54,39 @@ impl<F, S> BroadcastConfigurationWriter<F, S>
         Self {
             first_writer: first_writer,
             second_writer: second_writer,
+            policy: BroadcastPolicy::default(),
         }
     }
+
+    /// Uses the policy to decide whether a write succeeded.
+    pub fn with_policy(mut self, policy: BroadcastPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Writes the configuration to both writers, reporting the outcome of the `first` and `second` writer.
+    pub fn broadcast_configuration(&mut self,
+                                   configuration: &F::Configuration)
+                                   -> Map<Join<SettleWrite<F::WriteResult, F::Error>, SettleWrite<S::WriteResult, S::Error>>,
+                                          fn((Settled<F::Error>, Settled<S::Error>)) -> BroadcastReport<Either<F::Error, S::Error>>> {
+        let first_write = self.first_writer
+            .write_configuration(configuration)
+            .then(settle as fn(_) -> _);
+
+        let second_write = self.second_writer
+            .write_configuration(configuration)
+            .then(settle as fn(_) -> _);
+
+        first_write.join(second_write)
+            .map(report as fn(_) -> _)
+    }
 }
 
 impl<F, S> BroadcastConfigurationWriter<F, S> {
+    pub fn policy(&self) -> BroadcastPolicy {
+        self.policy
+    }
+
     pub fn push_writer_front<W>(self, writer: W) -> BroadcastConfigurationWriter<W, Self>
         where Self: ConfigurationWriter,
               W: ConfigurationWriter<Configuration = <Self as ConfigurationWriter>::Configuration>
@@ -43,20 +108,98 @@ impl<F, S> ConfigurationWriter for BroadcastConfigurationWriter<F, S>
 {
     type Configuration = F::Configuration;
     type Error = Either<F::Error, S::Error>;
-    type WriteResult = Map<Join<MapErr<F::WriteResult, fn(F::Error) -> Self::Error>,
-             MapErr<S::WriteResult, fn(S::Error) -> Self::Error>>,
-        fn(((), ())) -> ()>;
+    type WriteResult = Then<Join3<SettleWrite<F::WriteResult, F::Error>,
+                                  SettleWrite<S::WriteResult, S::Error>,
+                                  FutureResult<BroadcastPolicy, !>>,
+                            Result<(), Self::Error>,
+                            fn(Result<(Settled<F::Error>, Settled<S::Error>, BroadcastPolicy), !>) -> Result<(), Self::Error>>;
 
     fn write_configuration(&mut self, configuration: &Self::Configuration) -> Self::WriteResult {
         let first_write = self.first_writer
             .write_configuration(configuration)
-            .map_err(Either::Left as fn(F::Error) -> Self::Error);
+            .then(settle as fn(_) -> _);
 
         let second_write = self.second_writer
             .write_configuration(configuration)
-            .map_err(Either::Right as fn(S::Error) -> Self::Error);
+            .then(settle as fn(_) -> _);
 
-        first_write.join(second_write)
-            .map(drop)
+        first_write.join3(second_write, future::ok(self.policy))
+            .then(decide as fn(_) -> _)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationReader, ConfigurationWriter};
+    use broadcast::{BroadcastConfigurationWriter, BroadcastPolicy};
+    use closure::ClosureConfigurationWriter;
+    use memory::MemoryConfigurationAccessor;
+    use either::Either;
+    use futures::Future;
+    use futures::future::{err, FutureResult};
+
+    fn failing_writer() -> ClosureConfigurationWriter<fn(&u32) -> FutureResult<(), ()>, u32> {
+        fn fail(_: &u32) -> FutureResult<(), ()> {
+            err(())
+        }
+
+        ClosureConfigurationWriter::new(fail as fn(&u32) -> FutureResult<(), ()>)
+    }
+
+    #[test]
+    fn write_configuration_strict_one_failed_writes_other() {
+        // Arrange
+        let accessor = MemoryConfigurationAccessor::empty();
+        let mut writer = BroadcastConfigurationWriter::new(failing_writer(), accessor.clone());
+
+        // Act
+        let result = writer.write_configuration(&7).wait();
+
+        // Assert
+        assert_eq!(result, Err(Either::Left(())));
+        assert_eq!(accessor.read_configuration().wait(), Ok(7));
+    }
+
+    #[test]
+    fn write_configuration_best_effort_one_failed_succeeds() {
+        // Arrange
+        let accessor = MemoryConfigurationAccessor::empty();
+        let mut writer = BroadcastConfigurationWriter::new(accessor.clone(), failing_writer())
+            .with_policy(BroadcastPolicy::BestEffort);
+
+        // Act
+        let result = writer.write_configuration(&7).wait();
+
+        // Assert
+        assert_eq!(result, Ok(()));
+        assert_eq!(accessor.read_configuration().wait(), Ok(7));
+    }
+
+    #[test]
+    fn broadcast_configuration_reports_each_writer() {
+        // Arrange
+        let mut writer = BroadcastConfigurationWriter::new(failing_writer(), MemoryConfigurationAccessor::empty());
+
+        // Act
+        let report = writer.broadcast_configuration(&7).wait().unwrap();
+
+        // Assert
+        assert_eq!(report.succeeded(), vec!["second"]);
+        assert_eq!(report.failed(), vec![("first", &Either::Left(()))]);
+    }
+
+    #[test]
+    fn write_configuration_quorum_exceeding_writers_requires_both_writers() {
+        // Arrange
+        let accessor = MemoryConfigurationAccessor::empty();
+        let mut writer = BroadcastConfigurationWriter::new(accessor.clone(), failing_writer())
+            .with_policy(BroadcastPolicy::Quorum(3));
+
+        // Act
+        let result = writer.write_configuration(&7).wait();
+
+        // Assert
+        assert_eq!(result, Err(Either::Right(())));
+        assert_eq!(accessor.read_configuration().wait(), Ok(7));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,79 @@
+use super::BroadcastReport;
+
+/// Decides whether a broadcast succeeded from the outcome of each writer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastPolicy {
+    /// Every writer must take the configuration.
+    Strict,
+    /// At least one writer must take the configuration.
+    BestEffort,
+    /// At least the specified number of writers must take the configuration.
+    Quorum(usize),
+}
+
+impl Default for BroadcastPolicy {
+    fn default() -> Self {
+        BroadcastPolicy::Strict
+    }
+}
+
+impl BroadcastPolicy {
+    /// Returns whether a broadcast to the number of writers could satisfy this policy, a quorum must be of
+    /// at least one writer and no more than the number of writers.
+    pub fn is_achievable(&self, writers: usize) -> bool {
+        match *self {
+            BroadcastPolicy::Quorum(required) => required > 0 && required <= writers,
+            BroadcastPolicy::Strict | BroadcastPolicy::BestEffort => true,
+        }
+    }
+
+    /// Returns whether the outcome of a broadcast satisfies this policy.
+    pub fn is_satisfied<E>(&self, report: &BroadcastReport<E>) -> bool {
+        self.is_satisfied_by(report.succeeded().len(), report.outcomes().len())
+    }
+
+    /// Returns whether the number of writers which took the configuration, out of the number written to,
+    /// satisfies this policy.
+    pub fn is_satisfied_by(&self, succeeded: usize, writers: usize) -> bool {
+        match *self {
+            BroadcastPolicy::Strict => succeeded == writers,
+            BroadcastPolicy::BestEffort => succeeded > 0,
+            BroadcastPolicy::Quorum(required) => succeeded >= required,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use broadcast::{BroadcastOutcome, BroadcastPolicy, BroadcastReport};
+
+    fn report(results: &[bool]) -> BroadcastReport<()> {
+        BroadcastReport::new(results.iter()
+            .enumerate()
+            .map(|(index, &success)| BroadcastOutcome::new(index.to_string(), if success { Ok(()) } else { Err(()) }))
+            .collect())
+    }
+
+    #[test]
+    fn is_satisfied_applies_policy() {
+        // Arrange
+        let partial = report(&[true, false, true]);
+        let failed = report(&[false, false, false]);
+
+        // Assert
+        assert!(!BroadcastPolicy::Strict.is_satisfied(&partial));
+        assert!(BroadcastPolicy::BestEffort.is_satisfied(&partial));
+        assert!(!BroadcastPolicy::BestEffort.is_satisfied(&failed));
+        assert!(BroadcastPolicy::Quorum(2).is_satisfied(&partial));
+        assert!(!BroadcastPolicy::Quorum(3).is_satisfied(&partial));
+    }
+
+    #[test]
+    fn is_achievable_rejects_impossible_quorum() {
+        // Assert
+        assert!(!BroadcastPolicy::Quorum(0).is_achievable(3));
+        assert!(!BroadcastPolicy::Quorum(4).is_achievable(3));
+        assert!(BroadcastPolicy::Quorum(3).is_achievable(3));
+        assert!(BroadcastPolicy::Strict.is_achievable(0));
+    }
+}
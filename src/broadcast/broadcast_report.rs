@@ -0,0 +1,68 @@
+/// The result of writing a configuration to one writer of a `BroadcastWriterSet`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BroadcastOutcome<E> {
+    writer: String,
+    result: Result<(), E>,
+}
+
+impl<E> BroadcastOutcome<E> {
+    pub fn new<S: Into<String>>(writer: S, result: Result<(), E>) -> Self {
+        Self {
+            writer: writer.into(),
+            result: result,
+        }
+    }
+
+    /// Gets the name of the writer.
+    pub fn writer(&self) -> &str {
+        &self.writer
+    }
+
+    pub fn result(&self) -> &Result<(), E> {
+        &self.result
+    }
+
+    /// Returns whether the writer took the configuration.
+    pub fn is_success(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// The outcome of writing a configuration to every writer of a `BroadcastWriterSet`, in the order the
+/// writers were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BroadcastReport<E> {
+    outcomes: Vec<BroadcastOutcome<E>>,
+}
+
+impl<E> BroadcastReport<E> {
+    pub fn new(outcomes: Vec<BroadcastOutcome<E>>) -> Self {
+        Self { outcomes: outcomes }
+    }
+
+    pub fn outcomes(&self) -> &[BroadcastOutcome<E>] {
+        &self.outcomes
+    }
+
+    pub fn into_outcomes(self) -> Vec<BroadcastOutcome<E>> {
+        self.outcomes
+    }
+
+    /// Gets the names of the writers which took the configuration.
+    pub fn succeeded(&self) -> Vec<&str> {
+        self.outcomes.iter().filter(|outcome| outcome.is_success()).map(BroadcastOutcome::writer).collect()
+    }
+
+    /// Gets the names and errors of the writers which failed.
+    pub fn failed(&self) -> Vec<(&str, &E)> {
+        self.outcomes
+            .iter()
+            .filter_map(|outcome| outcome.result.as_ref().err().map(|err| (outcome.writer(), err)))
+            .collect()
+    }
+
+    /// Returns whether every writer took the configuration.
+    pub fn is_success(&self) -> bool {
+        self.outcomes.iter().all(BroadcastOutcome::is_success)
+    }
+}
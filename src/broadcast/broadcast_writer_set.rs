@@ -0,0 +1,295 @@
+use {BoxConfigurationWriter, ConfigurationWriter};
+use super::{BroadcastOutcome, BroadcastPolicy, BroadcastReport, BroadcastWriterSetError};
+use futures::{BoxFuture, Future};
+use futures::future::{err, join_all};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+/// A `ConfigurationWriter` which writes a configuration to a list of named writers.
+///
+/// Unlike `BroadcastConfigurationWriter` the writers may be of different types and their number need not be
+/// known at compile time, the error of each writer is converted into `E`. Every writer is written to even
+/// when others fail, and `broadcast_configuration` reports the outcome of each writer.
+///
+/// Whether a write succeeds is decided by a `BroadcastPolicy`, by default every writer must succeed. A quorum
+/// which the writers in the set cannot satisfy fails the write without writing to any writer.
+pub struct BroadcastWriterSet<C, E> {
+    writers: Vec<(String, BoxConfigurationWriter<C, E>)>,
+    policy: BroadcastPolicy,
+}
+
+impl<C, E> BroadcastWriterSet<C, E>
+    where C: Send + 'static,
+          E: Send + 'static
+{
+    /// Creates a new `BroadcastWriterSet<C, E>` without any writers.
+    pub fn new() -> Self {
+        Self {
+            writers: Vec::new(),
+            policy: BroadcastPolicy::default(),
+        }
+    }
+
+    /// Uses the policy to decide whether a write succeeded.
+    pub fn with_policy(mut self, policy: BroadcastPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn push<S, W>(&mut self, name: S, writer: W)
+        where S: Into<String>,
+              W: ConfigurationWriter<Configuration = C> + Send + 'static,
+              W::Error: Into<E>
+    {
+        self.writers.push((name.into(), BoxConfigurationWriter::new(writer)));
+    }
+
+    pub fn with_writer<S, W>(mut self, name: S, writer: W) -> Self
+        where S: Into<String>,
+              W: ConfigurationWriter<Configuration = C> + Send + 'static,
+              W::Error: Into<E>
+    {
+        self.push(name, writer);
+        self
+    }
+
+    /// Writes the configuration to every writer, reporting the outcome of each writer.
+    pub fn broadcast_configuration(&mut self, configuration: &C) -> BoxFuture<BroadcastReport<E>, !> {
+        let writes: Vec<_> = self.writers
+            .iter_mut()
+            .map(|&mut (ref name, ref mut writer)| {
+                let name = name.clone();
+                writer.write(configuration).then(move |result| Ok(BroadcastOutcome::new(name, result)))
+            })
+            .collect();
+
+        join_all(writes).map(BroadcastReport::new).boxed()
+    }
+
+    /// Writes the configuration to every writer, returning the outcome of each writer when the policy is
+    /// satisfied.
+    ///
+    /// Nothing is written when the policy is a quorum which the number of writers cannot satisfy.
+    pub fn broadcast_with_policy(&mut self, configuration: &C) -> BoxFuture<BroadcastReport<E>, BroadcastWriterSetError<E>> {
+        let policy = self.policy;
+        if !policy.is_achievable(self.writers.len()) {
+            return err(BroadcastWriterSetError::unachievable(policy, self.writers.len())).boxed();
+        }
+
+        self.broadcast_configuration(configuration)
+            .map_err(|never| never)
+            .and_then(move |report| if policy.is_satisfied(&report) {
+                Ok(report)
+            } else {
+                Err(BroadcastWriterSetError::new(policy, report))
+            })
+            .boxed()
+    }
+}
+
+impl<C, E> BroadcastWriterSet<C, E> {
+    pub fn policy(&self) -> BroadcastPolicy {
+        self.policy
+    }
+
+    /// Gets the names of the writers in the order they were added.
+    pub fn writer_names(&self) -> Vec<&str> {
+        self.writers.iter().map(|&(ref name, _)| name.as_str()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.writers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.writers.is_empty()
+    }
+}
+
+impl<C, E> Default for BroadcastWriterSet<C, E>
+    where C: Send + 'static,
+          E: Send + 'static
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, E> Debug for BroadcastWriterSet<C, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("BroadcastWriterSet")
+            .field("writers", &self.writer_names())
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl<C, E> ConfigurationWriter for BroadcastWriterSet<C, E>
+    where C: Send + 'static,
+          E: Debug + Send + 'static
+{
+    type Configuration = C;
+    type Error = BroadcastWriterSetError<E>;
+    type WriteResult = BoxFuture<(), Self::Error>;
+
+    /// Writes the configuration to every writer, failing if the outcome does not satisfy the policy.
+    fn write_configuration(&mut self, configuration: &Self::Configuration) -> Self::WriteResult {
+        self.broadcast_with_policy(configuration).map(drop).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationReader, ConfigurationWriter};
+    use broadcast::{BroadcastPolicy, BroadcastWriterSet};
+    use closure::ClosureConfigurationWriter;
+    use memory::MemoryConfigurationAccessor;
+    use futures::Future;
+    use futures::future::{err, FutureResult};
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum TestError {
+        Unavailable,
+    }
+
+    impl From<!> for TestError {
+        fn from(never: !) -> Self {
+            never
+        }
+    }
+
+    fn failing_writer() -> ClosureConfigurationWriter<fn(&u32) -> FutureResult<(), TestError>, u32> {
+        fn fail(_: &u32) -> FutureResult<(), TestError> {
+            err(TestError::Unavailable)
+        }
+
+        ClosureConfigurationWriter::new(fail as fn(&u32) -> FutureResult<(), TestError>)
+    }
+
+    #[test]
+    fn broadcast_configuration_reports_each_writer() {
+        // Arrange
+        let primary = MemoryConfigurationAccessor::empty();
+        let secondary = MemoryConfigurationAccessor::empty();
+
+        let mut writers = BroadcastWriterSet::<u32, TestError>::new()
+            .with_writer("primary", primary.clone())
+            .with_writer("mirror", failing_writer())
+            .with_writer("secondary", secondary.clone());
+
+        // Act
+        let report = writers.broadcast_configuration(&7).wait().unwrap();
+
+        // Assert
+        assert_eq!(report.succeeded(), vec!["primary", "secondary"]);
+        assert_eq!(report.failed(), vec![("mirror", &TestError::Unavailable)]);
+        assert_eq!(primary.read_configuration().wait(), Ok(7));
+        assert_eq!(secondary.read_configuration().wait(), Ok(7));
+    }
+
+    #[test]
+    fn write_configuration_any_failed_returns_report() {
+        // Arrange
+        let mut writers = BroadcastWriterSet::<u32, TestError>::new()
+            .with_writer("primary", MemoryConfigurationAccessor::empty())
+            .with_writer("mirror", failing_writer());
+
+        // Act
+        let error = writers.write_configuration(&7).wait().unwrap_err();
+
+        // Assert
+        assert_eq!(error.report().succeeded(), vec!["primary"]);
+    }
+
+    #[test]
+    fn write_configuration_quorum_reached_succeeds() {
+        // Arrange
+        let mut writers = BroadcastWriterSet::<u32, TestError>::new()
+            .with_writer("primary", MemoryConfigurationAccessor::empty())
+            .with_writer("mirror", failing_writer())
+            .with_writer("secondary", MemoryConfigurationAccessor::empty())
+            .with_policy(BroadcastPolicy::Quorum(2));
+
+        // Act
+        let result = writers.write_configuration(&7).wait();
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn write_configuration_best_effort_all_failed_returns_error() {
+        // Arrange
+        let mut writers = BroadcastWriterSet::<u32, TestError>::new()
+            .with_policy(BroadcastPolicy::BestEffort)
+            .with_writer("mirror", failing_writer());
+
+        // Act
+        let error = writers.write_configuration(&7).wait().unwrap_err();
+
+        // Assert
+        assert_eq!(error.policy(), BroadcastPolicy::BestEffort);
+        assert!(error.report().succeeded().is_empty());
+    }
+
+    #[test]
+    fn broadcast_with_policy_satisfied_returns_report() {
+        // Arrange
+        let mut writers = BroadcastWriterSet::<u32, TestError>::new()
+            .with_writer("primary", MemoryConfigurationAccessor::empty())
+            .with_writer("mirror", failing_writer())
+            .with_policy(BroadcastPolicy::BestEffort);
+
+        // Act
+        let report = writers.broadcast_with_policy(&7).wait().unwrap();
+
+        // Assert
+        assert_eq!(report.succeeded(), vec!["primary"]);
+        assert_eq!(report.failed(), vec![("mirror", &TestError::Unavailable)]);
+    }
+
+    #[test]
+    fn write_configuration_quorum_set_before_writers_succeeds() {
+        // Arrange
+        let mut writers = BroadcastWriterSet::<u32, TestError>::new()
+            .with_policy(BroadcastPolicy::Quorum(2))
+            .with_writer("primary", MemoryConfigurationAccessor::empty())
+            .with_writer("secondary", MemoryConfigurationAccessor::empty());
+
+        // Act
+        let result = writers.write_configuration(&7).wait();
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn broadcast_with_policy_quorum_exceeding_writers_writes_nothing() {
+        // Arrange
+        let primary = MemoryConfigurationAccessor::empty();
+        let mut writers = BroadcastWriterSet::<u32, TestError>::new()
+            .with_writer("primary", primary.clone())
+            .with_policy(BroadcastPolicy::Quorum(2));
+
+        // Act
+        let error = writers.broadcast_with_policy(&7).wait().unwrap_err();
+
+        // Assert
+        assert!(error.is_unachievable());
+        assert!(error.report().outcomes().is_empty());
+        assert!(primary.read_configuration().wait().is_err());
+    }
+
+    #[test]
+    fn write_configuration_quorum_of_none_returns_error() {
+        // Arrange
+        let mut writers = BroadcastWriterSet::<u32, TestError>::new()
+            .with_writer("primary", MemoryConfigurationAccessor::empty())
+            .with_policy(BroadcastPolicy::Quorum(0));
+
+        // Act
+        let error = writers.write_configuration(&7).wait().unwrap_err();
+
+        // Assert
+        assert!(error.is_unachievable());
+    }
+}
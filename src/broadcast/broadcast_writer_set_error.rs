@@ -0,0 +1,73 @@
+use super::{BroadcastPolicy, BroadcastReport};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The error when a `BroadcastWriterSet` did not write a configuration to enough of its writers to satisfy
+/// its policy, containing the outcome of every writer.
+///
+/// When the policy cannot be satisfied by the number of writers in the set nothing is written, and the report
+/// is empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BroadcastWriterSetError<E> {
+    policy: BroadcastPolicy,
+    writers: usize,
+    report: BroadcastReport<E>,
+}
+
+impl<E> BroadcastWriterSetError<E> {
+    pub fn new(policy: BroadcastPolicy, report: BroadcastReport<E>) -> Self {
+        Self {
+            policy: policy,
+            writers: report.outcomes().len(),
+            report: report,
+        }
+    }
+
+    /// Creates a new `BroadcastWriterSetError<E>` for a policy which the number of writers cannot satisfy.
+    pub fn unachievable(policy: BroadcastPolicy, writers: usize) -> Self {
+        Self {
+            policy: policy,
+            writers: writers,
+            report: BroadcastReport::new(Vec::new()),
+        }
+    }
+
+    /// Gets whether nothing was written because the policy cannot be satisfied by the number of writers.
+    pub fn is_unachievable(&self) -> bool {
+        !self.policy.is_achievable(self.writers)
+    }
+
+    /// Gets the policy which was not satisfied.
+    pub fn policy(&self) -> BroadcastPolicy {
+        self.policy
+    }
+
+    pub fn report(&self) -> &BroadcastReport<E> {
+        &self.report
+    }
+
+    pub fn into_report(self) -> BroadcastReport<E> {
+        self.report
+    }
+}
+
+impl<E: Display> Display for BroadcastWriterSetError<E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        if self.is_unachievable() {
+            return write!(f, "Broadcast failed {:?} cannot be satisfied by {} writers", self.policy, self.writers);
+        }
+
+        write!(f, "Broadcast failed {:?}", self.policy)?;
+        for (writer, err) in self.report.failed() {
+            write!(f, "\n{}: {}", writer, err)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<E: Error> Error for BroadcastWriterSetError<E> {
+    fn description(&self) -> &str {
+        "broadcast failed"
+    }
+}
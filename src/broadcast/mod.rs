@@ -1,2 +1,20 @@
 mod broadcast_configuration_writer;
 pub use self::broadcast_configuration_writer::*;
+
+mod broadcast_report;
+pub use self::broadcast_report::*;
+
+mod broadcast_policy;
+pub use self::broadcast_policy::*;
+
+mod broadcast_writer_set_error;
+pub use self::broadcast_writer_set_error::*;
+
+mod broadcast_writer_set;
+pub use self::broadcast_writer_set::*;
+
+mod transactional_broadcast_error;
+pub use self::transactional_broadcast_error::*;
+
+mod transactional_broadcast_writer;
+pub use self::transactional_broadcast_writer::*;
@@ -0,0 +1,59 @@
+use super::BroadcastReport;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionalBroadcastError<E> {
+    /// The current configuration of a target could not be read, nothing was written.
+    Read(BroadcastReport<E>),
+    /// A write failed, the targets which were written have been rolled back to their previous configuration.
+    /// A target which had no previous configuration is reported in the rollback with the error from reading it.
+    Write {
+        write: BroadcastReport<E>,
+        rollback: BroadcastReport<E>,
+    },
+}
+
+impl<E> TransactionalBroadcastError<E> {
+    /// Returns whether every target which was written was successfully restored, this is `true` when nothing was written.
+    pub fn is_rolled_back(&self) -> bool {
+        match *self {
+            TransactionalBroadcastError::Read(_) => true,
+            TransactionalBroadcastError::Write { ref rollback, .. } => rollback.is_success(),
+        }
+    }
+}
+
+impl<E: Display> Display for TransactionalBroadcastError<E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            TransactionalBroadcastError::Read(ref report) => {
+                write!(f, "Read Error")?;
+                for (target, err) in report.failed() {
+                    write!(f, "\n{}: {}", target, err)?;
+                }
+            }
+            TransactionalBroadcastError::Write { ref write, ref rollback } => {
+                write!(f, "Write Error")?;
+                for (target, err) in write.failed() {
+                    write!(f, "\n{}: {}", target, err)?;
+                }
+
+                for (target, err) in rollback.failed() {
+                    write!(f, "\nRollback {}: {}", target, err)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<E: Error> Error for TransactionalBroadcastError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            TransactionalBroadcastError::Read(_) => "reading the current configuration failed",
+            TransactionalBroadcastError::Write { .. } => "writing the configuration failed",
+        }
+    }
+}
@@ -0,0 +1,411 @@
+use {BoxConfigurationReader, BoxConfigurationWriter, ConfigurationReader, ConfigurationWriter};
+use super::{BroadcastOutcome, BroadcastReport, TransactionalBroadcastError};
+use futures::{BoxFuture, Future};
+use futures::future::{err, join_all, ok};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::iter::repeat;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+type Target<C, E> = (String, BoxConfigurationReader<C, E>, SharedTarget<BoxConfigurationWriter<C, E>>);
+
+/// Shares a target between the reader and writer it is boxed into.
+struct SharedTarget<A> {
+    target: Arc<Mutex<A>>,
+}
+
+impl<A> SharedTarget<A> {
+    fn new(target: A) -> Self {
+        Self { target: Arc::new(Mutex::new(target)) }
+    }
+
+    fn lock(&self) -> MutexGuard<A> {
+        // The lock is only held to start a read or write, should it panic the target is still usable
+        self.target.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<A> Clone for SharedTarget<A> {
+    fn clone(&self) -> Self {
+        Self { target: self.target.clone() }
+    }
+}
+
+impl<A: ConfigurationReader> ConfigurationReader for SharedTarget<A> {
+    type Configuration = A::Configuration;
+    type Error = A::Error;
+    type ReadResult = A::ReadResult;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        self.lock().read_configuration()
+    }
+}
+
+impl<A: ConfigurationWriter> ConfigurationWriter for SharedTarget<A> {
+    type Configuration = A::Configuration;
+    type Error = A::Error;
+    type WriteResult = A::WriteResult;
+
+    fn write_configuration(&mut self, configuration: &Self::Configuration) -> Self::WriteResult {
+        self.lock().write_configuration(configuration)
+    }
+}
+
+type NotFound<E> = Arc<Fn(&E) -> bool + Send + Sync>;
+
+/// A `ConfigurationWriter` which writes a configuration to every one of a list of named targets or to none of them.
+///
+/// The current configuration of every target is read before writing, if reading any target fails nothing
+/// is written. If writing to any target fails the targets which were written are restored to the
+/// configuration they held before, and the error reports the outcome of both the writes and the rollback.
+///
+/// A target whose read fails with an error recognised by `with_not_found` has no configuration yet, it is
+/// written to but cannot be restored, so its rollback is reported as failing with the error from the read.
+pub struct TransactionalBroadcastWriter<C, E> {
+    targets: Vec<Target<C, E>>,
+    is_not_found: NotFound<E>,
+}
+
+impl<C, E> TransactionalBroadcastWriter<C, E>
+    where C: Send + 'static,
+          E: Send + 'static
+{
+    /// Creates a new `TransactionalBroadcastWriter<C, E>` without any targets.
+    pub fn new() -> Self {
+        Self {
+            targets: Vec::new(),
+            is_not_found: Arc::new(|_: &E| false),
+        }
+    }
+
+    /// Treats a target whose read fails with an error matching the predicate as having no configuration yet,
+    /// rather than failing the write.
+    pub fn with_not_found<F>(mut self, is_not_found: F) -> Self
+        where F: Fn(&E) -> bool + Send + Sync + 'static
+    {
+        self.is_not_found = Arc::new(is_not_found);
+        self
+    }
+
+    pub fn push<S, A>(&mut self, name: S, target: A)
+        where S: Into<String>,
+              A: ConfigurationReader<Configuration = C> + ConfigurationWriter<Configuration = C> + Send + 'static,
+              <A as ConfigurationReader>::Error: Into<E>,
+              <A as ConfigurationWriter>::Error: Into<E>
+    {
+        let target = SharedTarget::new(target);
+        let reader = BoxConfigurationReader::new(target.clone());
+        let writer = BoxConfigurationWriter::new(target);
+
+        self.targets.push((name.into(), reader, SharedTarget::new(writer)));
+    }
+
+    pub fn with_target<S, A>(mut self, name: S, target: A) -> Self
+        where S: Into<String>,
+              A: ConfigurationReader<Configuration = C> + ConfigurationWriter<Configuration = C> + Send + 'static,
+              <A as ConfigurationReader>::Error: Into<E>,
+              <A as ConfigurationWriter>::Error: Into<E>
+    {
+        self.push(name, target);
+        self
+    }
+}
+
+impl<C, E> TransactionalBroadcastWriter<C, E> {
+    /// Gets the names of the targets in the order they were added.
+    pub fn target_names(&self) -> Vec<&str> {
+        self.targets.iter().map(|&(ref name, _, _)| name.as_str()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+}
+
+impl<C, E> Default for TransactionalBroadcastWriter<C, E>
+    where C: Send + 'static,
+          E: Send + 'static
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, E> Debug for TransactionalBroadcastWriter<C, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("TransactionalBroadcastWriter")
+            .field("targets", &self.target_names())
+            .finish()
+    }
+}
+
+/// Writes each configuration to the target at the same position, reporting the outcome of each write.
+fn write_targets<'a, C, E, I>(writes: I) -> BoxFuture<BroadcastReport<E>, !>
+    where C: 'a,
+          E: Send + 'static,
+          I: IntoIterator<Item = (&'a Target<C, E>, &'a C)>
+{
+    let writes: Vec<_> = writes.into_iter()
+        .map(|(&(ref name, _, ref writer), configuration)| {
+            let name = name.clone();
+            writer.lock()
+                .write(configuration)
+                .then(move |result| Ok(BroadcastOutcome::new(name, result)))
+        })
+        .collect();
+
+    join_all(writes).map(BroadcastReport::new).boxed()
+}
+
+impl<C, E> ConfigurationWriter for TransactionalBroadcastWriter<C, E>
+    where C: Clone + Send + 'static,
+          E: Debug + Send + 'static
+{
+    type Configuration = C;
+    type Error = TransactionalBroadcastError<E>;
+    type WriteResult = BoxFuture<(), Self::Error>;
+
+    fn write_configuration(&mut self, configuration: &Self::Configuration) -> Self::WriteResult {
+        let targets = self.targets.clone();
+        let is_not_found = self.is_not_found.clone();
+        let configuration = configuration.clone();
+
+        let reads: Vec<_> = targets.iter()
+            .map(|&(ref name, ref reader, _)| {
+                let name = name.clone();
+                reader.read().then(move |result| Ok((name, result)))
+            })
+            .collect();
+
+        join_all(reads)
+            .map_err(|never: !| never)
+            .and_then(move |reads| {
+                // The previous configuration of each target, or the error when the target has none
+                let mut previous = Vec::with_capacity(reads.len());
+                let mut outcomes = Vec::with_capacity(reads.len());
+                for (name, result) in reads {
+                    match result {
+                        Err(ref e) if !is_not_found(e) => {}
+                        previous_configuration => {
+                            previous.push(previous_configuration);
+                            outcomes.push(BroadcastOutcome::new(name, Ok(())));
+                            continue;
+                        }
+                    }
+
+                    outcomes.push(BroadcastOutcome::new(name, result.map(drop)));
+                }
+
+                if previous.len() != targets.len() {
+                    return err(TransactionalBroadcastError::Read(BroadcastReport::new(outcomes))).boxed();
+                }
+
+                write_targets(targets.iter().zip(repeat(&configuration)))
+                    .map_err(|never| never)
+                    .and_then(move |write| {
+                        if write.is_success() {
+                            return ok(()).boxed();
+                        }
+
+                        // Only the targets which took the new configuration need restoring
+                        let rollback: Vec<_> = targets.iter()
+                            .zip(previous)
+                            .zip(write.outcomes())
+                            .filter(|&(_, outcome)| outcome.is_success())
+                            .map(|((&(ref name, _, ref writer), previous), _)| {
+                                let name = name.clone();
+                                let restore = match previous {
+                                    Ok(configuration) => writer.lock().write(&configuration),
+                                    Err(e) => err(e).boxed(),
+                                };
+
+                                restore.then(move |result| Ok(BroadcastOutcome::new(name, result)))
+                            })
+                            .collect();
+
+                        join_all(rollback)
+                            .map(BroadcastReport::new)
+                            .map_err(|never: !| never)
+                            .and_then(move |rollback| {
+                                Err(TransactionalBroadcastError::Write {
+                                    write: write,
+                                    rollback: rollback,
+                                })
+                            })
+                            .boxed()
+                    })
+                    .boxed()
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationAccessor, ConfigurationReader, ConfigurationWriter};
+    use broadcast::{TransactionalBroadcastError, TransactionalBroadcastWriter};
+    use closure::{ClosureConfigurationReader, ClosureConfigurationWriter};
+    use memory::{MemoryConfigurationAccessor, MemoryConfigurationReadError};
+    use futures::Future;
+    use futures::future::{err, FutureResult};
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum TestError {
+        Missing,
+        Unavailable,
+    }
+
+    impl From<!> for TestError {
+        fn from(never: !) -> Self {
+            never
+        }
+    }
+
+    impl From<MemoryConfigurationReadError> for TestError {
+        fn from(_: MemoryConfigurationReadError) -> Self {
+            TestError::Missing
+        }
+    }
+
+    fn fail(_: &u32) -> FutureResult<(), TestError> {
+        err(TestError::Unavailable)
+    }
+
+    type FailingTarget = ConfigurationAccessor<MemoryConfigurationAccessor<u32>,
+                                               ClosureConfigurationWriter<fn(&u32) -> FutureResult<(), TestError>, u32>>;
+
+    fn failing_target() -> FailingTarget {
+        ConfigurationAccessor::new(MemoryConfigurationAccessor::new(1),
+                                   ClosureConfigurationWriter::new(fail as fn(&u32) -> FutureResult<(), TestError>))
+    }
+
+    fn fail_read() -> FutureResult<u32, TestError> {
+        err(TestError::Unavailable)
+    }
+
+    type UnreachableTarget = ConfigurationAccessor<ClosureConfigurationReader<fn() -> FutureResult<u32, TestError>>,
+                                                   MemoryConfigurationAccessor<u32>>;
+
+    fn unreachable_target() -> UnreachableTarget {
+        ConfigurationAccessor::new(ClosureConfigurationReader::new(fail_read as fn() -> FutureResult<u32, TestError>),
+                                   MemoryConfigurationAccessor::new(1))
+    }
+
+    #[test]
+    fn write_configuration_writes_every_target() {
+        // Arrange
+        let primary = MemoryConfigurationAccessor::new(1);
+        let secondary = MemoryConfigurationAccessor::new(1);
+
+        let mut writer = TransactionalBroadcastWriter::<u32, TestError>::new()
+            .with_target("primary", primary.clone())
+            .with_target("secondary", secondary.clone());
+
+        // Act
+        let result = writer.write_configuration(&2).wait();
+
+        // Assert
+        assert_eq!(result, Ok(()));
+        assert_eq!(primary.read_configuration().wait(), Ok(2));
+        assert_eq!(secondary.read_configuration().wait(), Ok(2));
+    }
+
+    #[test]
+    fn write_configuration_failed_write_restores_previous_configuration() {
+        // Arrange
+        let primary = MemoryConfigurationAccessor::new(1);
+        let secondary = MemoryConfigurationAccessor::new(1);
+
+        let mut writer = TransactionalBroadcastWriter::<u32, TestError>::new()
+            .with_target("primary", primary.clone())
+            .with_target("mirror", failing_target())
+            .with_target("secondary", secondary.clone());
+
+        // Act
+        let error = writer.write_configuration(&2).wait().unwrap_err();
+
+        // Assert
+        assert!(error.is_rolled_back());
+        match error {
+            TransactionalBroadcastError::Write { write, rollback } => {
+                assert_eq!(write.failed(), vec![("mirror", &TestError::Unavailable)]);
+                assert_eq!(rollback.succeeded(), vec!["primary", "secondary"]);
+            }
+            other => panic!("expected write error, got {:?}", other),
+        }
+        assert_eq!(primary.read_configuration().wait(), Ok(1));
+        assert_eq!(secondary.read_configuration().wait(), Ok(1));
+    }
+
+    #[test]
+    fn write_configuration_failed_read_writes_nothing() {
+        // Arrange
+        let primary = MemoryConfigurationAccessor::new(1);
+
+        let mut writer = TransactionalBroadcastWriter::<u32, TestError>::new()
+            .with_not_found(|e| *e == TestError::Missing)
+            .with_target("primary", primary.clone())
+            .with_target("unreachable", unreachable_target());
+
+        // Act
+        let error = writer.write_configuration(&2).wait().unwrap_err();
+
+        // Assert
+        match error {
+            TransactionalBroadcastError::Read(report) => {
+                assert_eq!(report.failed(), vec![("unreachable", &TestError::Unavailable)])
+            }
+            other => panic!("expected read error, got {:?}", other),
+        }
+        assert_eq!(primary.read_configuration().wait(), Ok(1));
+    }
+
+    #[test]
+    fn write_configuration_not_found_writes_empty_target() {
+        // Arrange
+        let empty = MemoryConfigurationAccessor::empty();
+
+        let mut writer = TransactionalBroadcastWriter::<u32, TestError>::new()
+            .with_not_found(|e| *e == TestError::Missing)
+            .with_target("primary", MemoryConfigurationAccessor::new(1))
+            .with_target("empty", empty.clone());
+
+        // Act
+        let result = writer.write_configuration(&2).wait();
+
+        // Assert
+        assert_eq!(result, Ok(()));
+        assert_eq!(empty.read_configuration().wait(), Ok(2));
+    }
+
+    #[test]
+    fn write_configuration_failed_write_reports_empty_target_not_restored() {
+        // Arrange
+        let primary = MemoryConfigurationAccessor::new(1);
+        let empty = MemoryConfigurationAccessor::empty();
+
+        let mut writer = TransactionalBroadcastWriter::<u32, TestError>::new()
+            .with_not_found(|e| *e == TestError::Missing)
+            .with_target("primary", primary.clone())
+            .with_target("empty", empty.clone())
+            .with_target("mirror", failing_target());
+
+        // Act
+        let error = writer.write_configuration(&2).wait().unwrap_err();
+
+        // Assert
+        assert!(!error.is_rolled_back());
+        match error {
+            TransactionalBroadcastError::Write { rollback, .. } => {
+                assert_eq!(rollback.succeeded(), vec!["primary"]);
+                assert_eq!(rollback.failed(), vec![("empty", &TestError::Missing)]);
+            }
+            other => panic!("expected write error, got {:?}", other),
+        }
+        assert_eq!(primary.read_configuration().wait(), Ok(1));
+        assert_eq!(empty.read_configuration().wait(), Ok(2));
+    }
+}
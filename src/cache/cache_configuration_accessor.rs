@@ -0,0 +1,337 @@
+use {ConfigurationAccessor, ConfigurationReader, ConfigurationWriter, CopyConfigurationError};
+use super::{CacheConfigurationReader, CacheHandle, CachePolicy, CacheWriteError, CacheWriteMode};
+use super::write_behind_queue::WriteBehindQueue;
+use futures::{BoxFuture, Future};
+use futures::future::SharedError;
+use futures::sync::oneshot;
+use std::panic::AssertUnwindSafe;
+
+/// A `ConfigurationReader` and `ConfigurationWriter` which caches the configuration of another accessor,
+/// keeping the cache consistent with the configurations written through it.
+///
+/// Reads behave as a `CacheConfigurationReader` of the reader. How writes update the cache is decided by a
+/// `CacheWriteMode`, by default a configuration is cached once it has been written, so a read after a
+/// successful write returns the written configuration.
+#[derive(Debug)]
+pub struct CacheConfigurationAccessor<C, R: ConfigurationReader, W> {
+    reader: CacheConfigurationReader<C, R>,
+    writer: W,
+    write_mode: CacheWriteMode,
+    write_behind: Option<WriteBehindQueue>,
+}
+
+impl<R, W> CacheConfigurationAccessor<R::Configuration, R, W>
+    where R: ConfigurationReader,
+          W: ConfigurationWriter<Configuration = R::Configuration>
+{
+    pub fn new<A>(accessor: A) -> Self
+        where A: Into<ConfigurationAccessor<R, W>>
+    {
+        let (reader, writer) = accessor.into().into();
+        let (reader, _) = CacheConfigurationReader::new(reader);
+
+        Self {
+            reader: reader,
+            writer: writer,
+            write_mode: CacheWriteMode::default(),
+            write_behind: None,
+        }
+    }
+
+    /// Uses the policy to decide when the cached configuration is refreshed.
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.reader = self.reader.with_policy(policy);
+        self
+    }
+
+    /// Uses the write mode to decide how writes update the cache.
+    pub fn with_write_mode(mut self, write_mode: CacheWriteMode) -> Self {
+        self.write_mode = write_mode;
+        self
+    }
+}
+
+impl<C, R: ConfigurationReader, W> CacheConfigurationAccessor<C, R, W> {
+    pub fn policy(&self) -> CachePolicy {
+        self.reader.policy()
+    }
+
+    pub fn write_mode(&self) -> CacheWriteMode {
+        self.write_mode
+    }
+
+    /// Gets a handle which clears or refreshes the cached configuration.
+    pub fn handle(&self) -> CacheHandle<C, R> {
+        self.reader.handle()
+    }
+}
+
+impl<R, W> ConfigurationReader for CacheConfigurationAccessor<R::Configuration, R, W>
+    where R: ConfigurationReader + Send + Sync + 'static,
+          R::Configuration: Clone + Sync,
+          R::Error: Sync
+{
+    type Configuration = R::Configuration;
+    type Error = CopyConfigurationError<SharedError<R::Error>, !>;
+    type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        self.reader.read_configuration()
+    }
+}
+
+impl<R, W> ConfigurationWriter for CacheConfigurationAccessor<R::Configuration, R, W>
+    where R: ConfigurationReader + Send + Sync + 'static,
+          R::Configuration: Clone + Sync,
+          R::Error: Sync,
+          W: ConfigurationWriter<Configuration = R::Configuration>
+{
+    type Configuration = R::Configuration;
+    type Error = CacheWriteError<W::Error>;
+    type WriteResult = BoxFuture<(), Self::Error>;
+
+    fn write_configuration(&mut self, configuration: &Self::Configuration) -> Self::WriteResult {
+        let state = self.reader.state().clone();
+
+        match self.write_mode {
+            CacheWriteMode::WriteThrough => {
+                let configuration = configuration.clone();
+                self.writer
+                    .write_configuration(&configuration)
+                    .map(move |_| {
+                        state.store(configuration);
+                    })
+                    .map_err(CacheWriteError::WriteError)
+                    .boxed()
+            }
+            CacheWriteMode::Invalidate => {
+                self.writer
+                    .write_configuration(configuration)
+                    .map(move |_| state.clear())
+                    .map_err(CacheWriteError::WriteError)
+                    .boxed()
+            }
+            CacheWriteMode::WriteBehind => {
+                let generation = state.store(configuration.clone());
+                let (written, write_result) = oneshot::channel();
+
+                // A writer which panics fails its write, the writes queued after it are still written
+                let write = AssertUnwindSafe(self.writer.write_configuration(configuration))
+                    .catch_unwind()
+                    .then(move |result| {
+                        let result = match result {
+                            Ok(Ok(())) => Ok(()),
+                            Ok(Err(e)) => Err(CacheWriteError::WriteError(e)),
+                            Err(_) => Err(CacheWriteError::WriteBehindPanicked),
+                        };
+
+                        if result.is_err() {
+                            state.clear_if_generation(generation);
+                        }
+
+                        // The write does not have to be waited on
+                        let _ = written.send(result);
+                        Ok(())
+                    })
+                    .boxed();
+
+                self.write_behind
+                    .get_or_insert_with(WriteBehindQueue::new)
+                    .push(write);
+
+                write_result.then(|written| match written {
+                        Ok(result) => result,
+                        Err(_) => Err(CacheWriteError::WriteBehindPanicked),
+                    })
+                    .boxed()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationAccessor, ConfigurationReader, ConfigurationWriter};
+    use cache::{CacheConfigurationAccessor, CacheWriteError, CacheWriteMode};
+    use closure::ClosureConfigurationWriter;
+    use memory::MemoryConfigurationAccessor;
+    use futures::Future;
+    use futures::future::{self, err, FutureResult};
+    use std::thread;
+    use std::time::Duration;
+
+    fn fail(_: &u32) -> FutureResult<(), &'static str> {
+        err("unavailable")
+    }
+
+    #[test]
+    fn write_configuration_write_through_reads_written_configuration() {
+        // Arrange
+        let origin = MemoryConfigurationAccessor::new(1);
+        let mut accessor = CacheConfigurationAccessor::new(origin.clone());
+        accessor.read_configuration().wait().unwrap();
+
+        // Act
+        accessor.write_configuration(&2).wait().unwrap();
+
+        // Assert
+        assert_eq!(accessor.read_configuration().wait().unwrap(), 2);
+        assert_eq!(origin.read_configuration().wait(), Ok(2));
+    }
+
+    #[test]
+    fn write_configuration_failed_write_keeps_cached_configuration() {
+        // Arrange
+        let origin = MemoryConfigurationAccessor::new(1);
+        let mut accessor = CacheConfigurationAccessor::new(ConfigurationAccessor::new(origin.clone(),
+                                                                                      ClosureConfigurationWriter::new(fail)));
+        accessor.read_configuration().wait().unwrap();
+
+        // Act
+        let result = accessor.write_configuration(&2).wait();
+
+        // Assert
+        assert_eq!(result, Err(CacheWriteError::WriteError("unavailable")));
+        assert_eq!(accessor.read_configuration().wait().unwrap(), 1);
+    }
+
+    #[test]
+    fn write_configuration_invalidate_clears_cache() {
+        // Arrange
+        let origin = MemoryConfigurationAccessor::new(1);
+        let mut accessor = CacheConfigurationAccessor::new(origin.clone()).with_write_mode(CacheWriteMode::Invalidate);
+        accessor.read_configuration().wait().unwrap();
+
+        // Act
+        accessor.write_configuration(&2).wait().unwrap();
+
+        // Assert
+        assert!(!accessor.handle().is_cached());
+        assert_eq!(accessor.read_configuration().wait().unwrap(), 2);
+    }
+
+    #[test]
+    fn write_configuration_write_behind_caches_then_writes() {
+        // Arrange
+        let origin = MemoryConfigurationAccessor::new(1);
+        let mut accessor = CacheConfigurationAccessor::new(origin.clone()).with_write_mode(CacheWriteMode::WriteBehind);
+
+        // Act
+        accessor.write_configuration(&2).wait().unwrap();
+
+        // Assert
+        assert_eq!(accessor.read_configuration().wait().unwrap(), 2);
+        for _ in 0..100 {
+            if origin.read_configuration().wait() == Ok(2) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("the configuration was never written behind");
+    }
+
+    #[test]
+    fn write_configuration_write_behind_failed_write_clears_cache() {
+        // Arrange
+        let origin = MemoryConfigurationAccessor::new(1);
+        let mut accessor = CacheConfigurationAccessor::new(ConfigurationAccessor::new(origin.clone(),
+                                                                                      ClosureConfigurationWriter::new(fail)))
+            .with_write_mode(CacheWriteMode::WriteBehind);
+
+        // Act
+        let result = accessor.write_configuration(&2).wait();
+
+        // Assert
+        assert_eq!(result, Err(CacheWriteError::WriteError("unavailable")));
+        assert!(!accessor.handle().is_cached());
+        assert_eq!(accessor.read_configuration().wait().unwrap(), 1);
+    }
+
+    #[test]
+    fn write_configuration_write_behind_panicking_writer_fails_write() {
+        // Arrange
+        let origin = MemoryConfigurationAccessor::new(1);
+        let written = origin.clone();
+        let writer = ClosureConfigurationWriter::new(move |configuration: &u32| {
+            let mut written = written.clone();
+            let configuration = *configuration;
+            future::lazy(move || {
+                if configuration == 2 {
+                    panic!("writer panicked");
+                }
+                let _ = written.write_configuration(&configuration).wait();
+                Ok::<(), &'static str>(())
+            })
+        });
+        let mut accessor = CacheConfigurationAccessor::new(ConfigurationAccessor::new(origin.clone(), writer))
+            .with_write_mode(CacheWriteMode::WriteBehind);
+
+        // Act
+        let panicked = accessor.write_configuration(&2).wait();
+        let written = accessor.write_configuration(&3).wait();
+
+        // Assert
+        assert_eq!(panicked, Err(CacheWriteError::WriteBehindPanicked));
+        assert_eq!(written, Ok(()));
+        assert_eq!(origin.read_configuration().wait(), Ok(3));
+        assert_eq!(accessor.read_configuration().wait().unwrap(), 3);
+    }
+
+    #[test]
+    fn write_configuration_write_behind_writes_in_order() {
+        // Arrange
+        let origin = MemoryConfigurationAccessor::new(0);
+        let written = origin.clone();
+        let writer = ClosureConfigurationWriter::new(move |configuration: &u32| {
+            // Earlier writes take longer, so writes run concurrently would complete out of order
+            let mut written = written.clone();
+            let configuration = *configuration;
+            future::lazy(move || {
+                thread::sleep(Duration::from_millis(10 * (5 - configuration as u64)));
+                let _ = written.write_configuration(&configuration).wait();
+                Ok::<(), &'static str>(())
+            })
+        });
+        let mut accessor = CacheConfigurationAccessor::new(ConfigurationAccessor::new(origin.clone(), writer))
+            .with_write_mode(CacheWriteMode::WriteBehind);
+
+        // Act
+        let writes: Vec<_> = (1..5).map(|configuration| accessor.write_configuration(&configuration)).collect();
+        for write in writes {
+            write.wait().unwrap();
+        }
+
+        // Assert
+        assert_eq!(origin.read_configuration().wait(), Ok(4));
+    }
+
+    #[test]
+    fn write_configuration_write_behind_failed_write_keeps_later_configuration() {
+        // Arrange
+        let origin = MemoryConfigurationAccessor::new(1);
+        let written = origin.clone();
+        let writer = ClosureConfigurationWriter::new(move |configuration: &u32| {
+            let mut written = written.clone();
+            let configuration = *configuration;
+            future::lazy(move || {
+                if configuration == 2 {
+                    return Err("unavailable");
+                }
+                let _ = written.write_configuration(&configuration).wait();
+                Ok(())
+            })
+        });
+        let mut accessor = CacheConfigurationAccessor::new(ConfigurationAccessor::new(origin.clone(), writer))
+            .with_write_mode(CacheWriteMode::WriteBehind);
+
+        // Act
+        let failed = accessor.write_configuration(&2);
+        let succeeded = accessor.write_configuration(&3);
+
+        // Assert
+        assert_eq!(failed.wait(), Err(CacheWriteError::WriteError("unavailable")));
+        assert_eq!(succeeded.wait(), Ok(()));
+        assert!(accessor.handle().is_cached());
+        assert_eq!(accessor.read_configuration().wait().unwrap(), 3);
+    }
+}
@@ -1,41 +1,288 @@
-use {ConfigurationReader, ConfigurationWriter, CopyConfigurationError};
-use memory::MemoryConfigurationAccessor;
+use {ConfigurationReader, CopyConfigurationError};
+use super::{CacheFreshness, CacheHandle, CachePolicy};
+use super::cache_state::CacheState;
 use futures::{Future, BoxFuture};
-use std::clone::Clone;
-use copy_configuration;
+use futures::future::{ok, SharedError};
+use std::sync::Arc;
 
+/// A `ConfigurationReader` which caches the configuration read by another reader.
+///
+/// When the cached configuration is refreshed is decided by a `CachePolicy`, by default the configuration is
+/// read once and cached forever. A stale configuration is returned at once while it is refreshed, unless it
+/// is already being read. No thread is started for the refresh, it is driven by the stale reads which follow
+/// until it completes.
+///
+/// Concurrent reads which need to read the configuration share one read of the reader, each receiving its
+/// configuration or error. The cached configuration can be cleared or refreshed through the `CacheHandle`
+/// returned by `new`.
 #[derive(Debug)]
-pub struct CacheConfigurationReader<C, R> {
-    memory: MemoryConfigurationAccessor<C>,
-    reader: R,
+pub struct CacheConfigurationReader<C, R: ConfigurationReader> {
+    state: Arc<CacheState<C, R>>,
+    policy: CachePolicy,
 }
 
 impl<R> CacheConfigurationReader<R::Configuration, R>
     where R: ConfigurationReader
 {
-    pub fn new(reader: R) -> Self {
+    /// Creates a new `CacheConfigurationReader` of the reader, returning it with a handle which clears or
+    /// refreshes the cached configuration.
+    pub fn new(reader: R) -> (Self, CacheHandle<R::Configuration, R>) {
+        let reader = Self {
+            state: Arc::new(CacheState::new(reader)),
+            policy: CachePolicy::default(),
+        };
+        let handle = reader.handle();
+
+        (reader, handle)
+    }
+
+    /// Uses the policy to decide when the cached configuration is refreshed.
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+impl<C, R: ConfigurationReader> CacheConfigurationReader<C, R> {
+    pub fn policy(&self) -> CachePolicy {
+        self.policy
+    }
+
+    pub(super) fn state(&self) -> &Arc<CacheState<C, R>> {
+        &self.state
+    }
+
+    /// Gets a handle which clears or refreshes the configuration cached by this reader and its clones.
+    pub fn handle(&self) -> CacheHandle<C, R> {
+        CacheHandle::new(self.state.clone())
+    }
+}
+
+impl<C, R: ConfigurationReader> Clone for CacheConfigurationReader<C, R> {
+    fn clone(&self) -> Self {
+        // Clone should share the same cache
         Self {
-            memory: MemoryConfigurationAccessor::empty(),
-            reader: reader,
+            state: self.state.clone(),
+            policy: self.policy,
         }
     }
 }
 
 impl<R> ConfigurationReader for CacheConfigurationReader<R::Configuration, R>
-    where R: ConfigurationReader + Send + Clone + 'static,
-          R::Configuration: Clone + Sync
+    where R: ConfigurationReader + Send + Sync + 'static,
+          R::Configuration: Clone + Sync,
+          R::Error: Sync
 {
     type Configuration = R::Configuration;
-    type Error = CopyConfigurationError<R::Error, <MemoryConfigurationAccessor<Self::Configuration> as ConfigurationWriter>::Error>;
+    type Error = CopyConfigurationError<SharedError<R::Error>, !>;
     type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
 
     fn read_configuration(&self) -> Self::ReadResult {
-        let reader_clone = self.reader.clone();
-        let memory_clone = self.memory.clone();
+        let (configuration, age) = match self.state.cached() {
+            Some(cached) => cached,
+            None => return CacheState::refresh(&self.state).map_err(CopyConfigurationError::ReadError).boxed(),
+        };
 
-        self.memory
-            .read_configuration()
-            .or_else(move |_| copy_configuration(&reader_clone, memory_clone))
-            .boxed()
+        let policy = self.policy;
+        match policy.freshness(age) {
+            CacheFreshness::Fresh => ok(configuration).boxed(),
+            CacheFreshness::Stale => CacheState::revalidate(&self.state).then(move |_| Ok(configuration)).boxed(),
+            CacheFreshness::Expired => {
+                CacheState::refresh(&self.state)
+                    .or_else(move |e| if policy.is_usable_on_error(age) {
+                        Ok(configuration)
+                    } else {
+                        Err(CopyConfigurationError::ReadError(e))
+                    })
+                    .boxed()
+            }
+        }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationReader, ConfigurationWriter, FluentConfigurationReader};
+    use cache::{CacheConfigurationReader, CachePolicy};
+    use memory::MemoryConfigurationAccessor;
+    use closure::ClosureConfigurationReader;
+    use futures::Future;
+    use futures::future::ok;
+    use futures::sync::oneshot;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn read_configuration_default_policy_caches_forever() {
+        // Arrange
+        let mut origin = MemoryConfigurationAccessor::new(1);
+        let (reader, _) = CacheConfigurationReader::new(origin.clone());
+        reader.read_configuration().wait().unwrap();
+
+        // Act
+        origin.write_configuration(&2).wait().unwrap();
+        let configuration = reader.read_configuration().wait();
+
+        // Assert
+        assert_eq!(configuration.unwrap(), 1);
+    }
+
+    #[test]
+    fn read_configuration_expired_reads_again() {
+        // Arrange
+        let mut origin = MemoryConfigurationAccessor::new(1);
+        let (reader, _) = CacheConfigurationReader::new(origin.clone());
+        let reader = reader.with_policy(CachePolicy::new().with_time_to_live(Duration::from_secs(0)));
+        reader.read_configuration().wait().unwrap();
+
+        // Act
+        origin.write_configuration(&2).wait().unwrap();
+        let configuration = reader.read_configuration().wait();
+
+        // Assert
+        assert_eq!(configuration.unwrap(), 2);
+    }
+
+    #[test]
+    fn read_configuration_stale_while_revalidate_returns_stale_then_refreshes() {
+        // Arrange
+        let mut origin = MemoryConfigurationAccessor::new(1);
+        let (reader, _) = CacheConfigurationReader::new(origin.clone());
+        let reader = reader.with_policy(CachePolicy::new()
+            .with_time_to_live(Duration::from_secs(0))
+            .with_stale_while_revalidate(Duration::from_secs(3600)));
+        reader.read_configuration().wait().unwrap();
+
+        // Act
+        origin.write_configuration(&2).wait().unwrap();
+        let stale = reader.read_configuration().wait();
+
+        // Assert
+        assert_eq!(stale.unwrap(), 1);
+        for _ in 0..100 {
+            if reader.read_configuration().wait().ok() == Some(2) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("the stale configuration was never refreshed");
+    }
+
+    #[test]
+    fn read_configuration_stale_if_error_returns_stale() {
+        // Arrange
+        let mut origin = MemoryConfigurationAccessor::new(Some(1));
+        let (reader, _) = CacheConfigurationReader::new(origin.clone().and_then(|value: Option<u32>| value.ok_or("unavailable")));
+        let reader = reader.with_policy(CachePolicy::new()
+            .with_time_to_live(Duration::from_secs(0))
+            .with_stale_if_error(Duration::from_secs(3600)));
+        reader.read_configuration().wait().unwrap();
+
+        // Act
+        origin.write_configuration(&None).wait().unwrap();
+        let configuration = reader.read_configuration().wait();
+
+        // Assert
+        assert_eq!(configuration.unwrap(), 1);
+    }
+
+    #[test]
+    fn read_configuration_expired_without_stale_if_error_returns_error() {
+        // Arrange
+        let mut origin = MemoryConfigurationAccessor::new(Some(1));
+        let (reader, _) = CacheConfigurationReader::new(origin.clone().and_then(|value: Option<u32>| value.ok_or("unavailable")));
+        let reader = reader.with_policy(CachePolicy::new().with_time_to_live(Duration::from_secs(0)));
+        reader.read_configuration().wait().unwrap();
+
+        // Act
+        origin.write_configuration(&None).wait().unwrap();
+        let configuration = reader.read_configuration().wait();
+
+        // Assert
+        assert!(configuration.is_err());
+    }
+
+    #[test]
+    fn read_configuration_concurrent_misses_share_one_read() {
+        // Arrange
+        let reads = Arc::new(AtomicUsize::new(0));
+        let (complete, completed) = oneshot::channel::<u32>();
+        let completed = completed.shared();
+
+        let reader_reads = reads.clone();
+        let (reader, _) = CacheConfigurationReader::new(ClosureConfigurationReader::new(move || {
+            reader_reads.fetch_add(1, Ordering::SeqCst);
+            completed.clone().map(|configuration| *configuration).map_err(|_| "cancelled")
+        }));
+
+        // Act
+        let pending: Vec<_> = (0..3).map(|_| reader.read_configuration()).collect();
+        complete.send(4).unwrap();
+        let configurations: Vec<_> = pending.into_iter().map(|read| read.wait().unwrap()).collect();
+
+        // Assert
+        assert_eq!(reads.load(Ordering::SeqCst), 1);
+        assert_eq!(configurations, vec![4, 4, 4]);
+    }
+
+    #[test]
+    fn read_configuration_stale_reads_drive_refresh_to_completion() {
+        // Arrange
+        let reads = Arc::new(AtomicUsize::new(0));
+        let (complete, completed) = oneshot::channel::<u32>();
+        let completed = completed.shared();
+
+        let reader_reads = reads.clone();
+        let (reader, _) = CacheConfigurationReader::new(ClosureConfigurationReader::new(move || {
+            let configuration: Box<Future<Item = u32, Error = &'static str> + Send> = match reader_reads.fetch_add(1, Ordering::SeqCst) {
+                0 => Box::new(ok(1)),
+                _ => Box::new(completed.clone().map(|configuration| *configuration).map_err(|_| "cancelled")),
+            };
+            configuration
+        }));
+        let reader = reader.with_policy(CachePolicy::new()
+                .with_time_to_live(Duration::from_secs(0))
+                .with_stale_while_revalidate(Duration::from_secs(3600)));
+        reader.read_configuration().wait().unwrap();
+        let started = reader.read_configuration().wait().unwrap();
+        complete.send(2).unwrap();
+
+        // Act
+        let completing = reader.read_configuration().wait().unwrap();
+        let refreshed = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!((started, completing, refreshed), (1, 1, 2));
+    }
+
+    #[test]
+    fn read_configuration_stale_while_refreshing_starts_one_refresh() {
+        // Arrange
+        let reads = Arc::new(AtomicUsize::new(0));
+        let (complete, completed) = oneshot::channel::<u32>();
+        let completed = completed.shared();
+
+        let reader_reads = reads.clone();
+        let (reader, _) = CacheConfigurationReader::new(ClosureConfigurationReader::new(move || {
+            let configuration: Box<Future<Item = u32, Error = &'static str> + Send> = match reader_reads.fetch_add(1, Ordering::SeqCst) {
+                0 => Box::new(ok(1)),
+                _ => Box::new(completed.clone().map(|configuration| *configuration).map_err(|_| "cancelled")),
+            };
+            configuration
+        }));
+        let reader = reader.with_policy(CachePolicy::new()
+                .with_time_to_live(Duration::from_secs(0))
+                .with_stale_while_revalidate(Duration::from_secs(3600)));
+        reader.read_configuration().wait().unwrap();
+
+        // Act
+        let stale: Vec<_> = (0..3).map(|_| reader.read_configuration().wait().unwrap()).collect();
+        complete.send(2).unwrap();
+
+        // Assert
+        assert_eq!(stale, vec![1, 1, 1]);
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,139 @@
+use ConfigurationReader;
+use super::cache_state::CacheState;
+use futures::BoxFuture;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// A handle to the configuration cached by a `CacheConfigurationReader`, returned when the reader is created.
+///
+/// The handle shares the cache of the reader it was taken from, so clearing or refreshing through the handle
+/// is seen by every clone of the reader.
+pub struct CacheHandle<C, R: ConfigurationReader> {
+    state: Arc<CacheState<C, R>>,
+}
+
+impl<C, R: ConfigurationReader> CacheHandle<C, R> {
+    pub(super) fn new(state: Arc<CacheState<C, R>>) -> Self {
+        Self { state: state }
+    }
+
+    /// Clears the cached configuration, the next read reads the configuration again.
+    pub fn clear(&self) {
+        self.state.clear();
+    }
+
+    /// Gets whether a configuration is cached.
+    pub fn is_cached(&self) -> bool {
+        self.state.cached_at().is_some()
+    }
+
+    /// Gets when the cached configuration was cached.
+    pub fn cached_at(&self) -> Option<Instant> {
+        self.state.cached_at()
+    }
+}
+
+impl<R> CacheHandle<R::Configuration, R>
+    where R: ConfigurationReader + Send + Sync + 'static,
+          R::Configuration: Clone + Sync,
+          R::Error: Sync
+{
+    /// Reads the configuration again, caching it when the read succeeds.
+    ///
+    /// A read already in flight is not shared, so the configuration is read after `refresh` is called. The cached
+    /// configuration is kept when the read fails.
+    pub fn refresh(&self) -> BoxFuture<R::Configuration, R::Error> {
+        CacheState::force_refresh(&self.state)
+    }
+}
+
+impl<C, R: ConfigurationReader> Clone for CacheHandle<C, R> {
+    fn clone(&self) -> Self {
+        Self { state: self.state.clone() }
+    }
+}
+
+impl<C, R: ConfigurationReader> Debug for CacheHandle<C, R> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("CacheHandle")
+            .field("cached_at", &self.cached_at())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationReader, ConfigurationWriter};
+    use cache::CacheConfigurationReader;
+    use closure::ClosureConfigurationReader;
+    use memory::MemoryConfigurationAccessor;
+    use futures::Future;
+    use futures::future::ok;
+    use futures::sync::oneshot;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn clear_reads_configuration_again() {
+        // Arrange
+        let mut origin = MemoryConfigurationAccessor::new(1);
+        let (reader, handle) = CacheConfigurationReader::new(origin.clone());
+        reader.read_configuration().wait().unwrap();
+        origin.write_configuration(&2).wait().unwrap();
+
+        // Act
+        handle.clear();
+
+        // Assert
+        assert!(!handle.is_cached());
+        assert_eq!(reader.read_configuration().wait().unwrap(), 2);
+        assert!(handle.is_cached());
+    }
+
+    #[test]
+    fn refresh_caches_new_configuration() {
+        // Arrange
+        let mut origin = MemoryConfigurationAccessor::new(1);
+        let (reader, handle) = CacheConfigurationReader::new(origin.clone());
+        reader.read_configuration().wait().unwrap();
+        let cached_at = handle.cached_at().unwrap();
+        origin.write_configuration(&2).wait().unwrap();
+
+        // Act
+        let refreshed = handle.refresh().wait().unwrap();
+
+        // Assert
+        assert_eq!(refreshed, 2);
+        assert_eq!(reader.read_configuration().wait().unwrap(), 2);
+        assert!(handle.cached_at().unwrap() >= cached_at);
+    }
+
+    #[test]
+    fn refresh_does_not_share_read_in_flight() {
+        // Arrange
+        let reads = Arc::new(AtomicUsize::new(0));
+        let (complete, completed) = oneshot::channel::<u32>();
+        let completed = completed.shared();
+
+        let reader_reads = reads.clone();
+        let (reader, handle) = CacheConfigurationReader::new(ClosureConfigurationReader::new(move || {
+            let configuration: Box<Future<Item = u32, Error = &'static str> + Send> = match reader_reads.fetch_add(1, Ordering::SeqCst) {
+                0 => Box::new(completed.clone().map(|configuration| *configuration).map_err(|_| "cancelled")),
+                _ => Box::new(ok(2)),
+            };
+            configuration
+        }));
+        let in_flight = reader.read_configuration();
+
+        // Act
+        let refreshed = handle.refresh().wait().unwrap();
+        complete.send(1).unwrap();
+        in_flight.wait().unwrap();
+
+        // Assert
+        assert_eq!(refreshed, 2);
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+        assert_eq!(reader.read_configuration().wait().unwrap(), 2);
+    }
+}
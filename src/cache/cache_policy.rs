@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+/// How fresh a cached configuration is according to a `CachePolicy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheFreshness {
+    /// The cached configuration can be returned as it is.
+    Fresh,
+    /// The cached configuration can be returned while it is refreshed in the background.
+    Stale,
+    /// The cached configuration must be refreshed before it is returned.
+    Expired,
+}
+
+/// Decides how long a `CacheConfigurationReader` returns a cached configuration before reading it again.
+///
+/// By default a cached configuration never expires. With a time-to-live the configuration is read again
+/// once it is older than the time-to-live, with stale-while-revalidate the old configuration is returned
+/// for a while longer as it is read again in the background, and with stale-if-error the old configuration
+/// is returned for a while longer when reading it again fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CachePolicy {
+    time_to_live: Option<Duration>,
+    stale_while_revalidate: Option<Duration>,
+    stale_if_error: Option<Duration>,
+}
+
+/// Gets whether the age is less than the time-to-live plus the stale duration, a sum too large to represent
+/// is never reached.
+fn is_within(age: Duration, time_to_live: Duration, stale: Duration) -> bool {
+    time_to_live.checked_add(stale).map_or(true, |limit| age < limit)
+}
+
+impl CachePolicy {
+    /// Creates a new `CachePolicy` where a cached configuration never expires.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expires a cached configuration once it is older than the time-to-live.
+    pub fn with_time_to_live(mut self, time_to_live: Duration) -> Self {
+        self.time_to_live = Some(time_to_live);
+        self
+    }
+
+    /// Returns an expired configuration for up to `stale_while_revalidate` after its time-to-live while it is
+    /// read again in the background.
+    pub fn with_stale_while_revalidate(mut self, stale_while_revalidate: Duration) -> Self {
+        self.stale_while_revalidate = Some(stale_while_revalidate);
+        self
+    }
+
+    /// Returns an expired configuration for up to `stale_if_error` after its time-to-live when reading it
+    /// again fails.
+    pub fn with_stale_if_error(mut self, stale_if_error: Duration) -> Self {
+        self.stale_if_error = Some(stale_if_error);
+        self
+    }
+
+    pub fn time_to_live(&self) -> Option<Duration> {
+        self.time_to_live
+    }
+
+    pub fn stale_while_revalidate(&self) -> Option<Duration> {
+        self.stale_while_revalidate
+    }
+
+    pub fn stale_if_error(&self) -> Option<Duration> {
+        self.stale_if_error
+    }
+
+    /// Gets the freshness of a cached configuration of the specified age.
+    pub fn freshness(&self, age: Duration) -> CacheFreshness {
+        let time_to_live = match self.time_to_live {
+            Some(time_to_live) => time_to_live,
+            None => return CacheFreshness::Fresh,
+        };
+
+        if age < time_to_live {
+            CacheFreshness::Fresh
+        } else if self.stale_while_revalidate.map_or(false, |stale| is_within(age, time_to_live, stale)) {
+            CacheFreshness::Stale
+        } else {
+            CacheFreshness::Expired
+        }
+    }
+
+    /// Gets whether a cached configuration of the specified age may be returned when reading it again fails.
+    pub fn is_usable_on_error(&self, age: Duration) -> bool {
+        match (self.time_to_live, self.stale_if_error) {
+            (None, _) => true,
+            (Some(time_to_live), Some(stale)) => is_within(age, time_to_live, stale),
+            (Some(time_to_live), None) => age < time_to_live,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cache::{CacheFreshness, CachePolicy};
+    use std::time::Duration;
+
+    #[test]
+    fn freshness_follows_time_to_live_and_stale_while_revalidate() {
+        // Arrange
+        let policy = CachePolicy::new()
+            .with_time_to_live(Duration::from_secs(60))
+            .with_stale_while_revalidate(Duration::from_secs(30));
+
+        // Act
+        let freshness: Vec<_> = [0, 59, 60, 89, 90].iter().map(|&age| policy.freshness(Duration::from_secs(age))).collect();
+
+        // Assert
+        assert_eq!(freshness,
+                   vec![CacheFreshness::Fresh,
+                        CacheFreshness::Fresh,
+                        CacheFreshness::Stale,
+                        CacheFreshness::Stale,
+                        CacheFreshness::Expired]);
+    }
+
+    #[test]
+    fn is_usable_on_error_follows_stale_if_error() {
+        // Arrange
+        let policy = CachePolicy::new()
+            .with_time_to_live(Duration::from_secs(60))
+            .with_stale_if_error(Duration::from_secs(300));
+
+        // Act
+        let usable = policy.is_usable_on_error(Duration::from_secs(359));
+        let unusable = policy.is_usable_on_error(Duration::from_secs(360));
+
+        // Assert
+        assert!(usable);
+        assert!(!unusable);
+    }
+
+    #[test]
+    fn freshness_unbounded_stale_while_revalidate_is_stale() {
+        // Arrange
+        let policy = CachePolicy::new()
+            .with_time_to_live(Duration::from_secs(60))
+            .with_stale_while_revalidate(Duration::new(u64::max_value(), 0))
+            .with_stale_if_error(Duration::new(u64::max_value(), 0));
+
+        // Act
+        let freshness = policy.freshness(Duration::from_secs(3600));
+        let usable = policy.is_usable_on_error(Duration::from_secs(3600));
+
+        // Assert
+        assert_eq!(freshness, CacheFreshness::Stale);
+        assert!(usable);
+    }
+}
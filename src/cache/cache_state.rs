@@ -0,0 +1,145 @@
+use ConfigurationReader;
+use sync::SingleFlight;
+use futures::{BoxFuture, Future};
+use futures::future::{ok, SharedError};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct CacheEntry<C> {
+    configuration: C,
+    cached_at: Instant,
+}
+
+/// The state shared by a `CacheConfigurationReader`, its clones and its `CacheHandle`s.
+#[derive(Debug)]
+pub(super) struct CacheState<C, R: ConfigurationReader> {
+    entry: Mutex<Option<CacheEntry<C>>>,
+    // Changes whenever the entry is replaced or cleared, so a read started before is not cached over it
+    generation: AtomicUsize,
+    refresh: SingleFlight<C, R::Error>,
+    reader: R,
+}
+
+impl<C, R: ConfigurationReader> CacheState<C, R> {
+    pub(super) fn new(reader: R) -> Self {
+        Self {
+            entry: Mutex::new(None),
+            generation: AtomicUsize::new(0),
+            refresh: SingleFlight::new(),
+            reader: reader,
+        }
+    }
+
+    pub(super) fn clear(&self) {
+        let mut entry = self.entry.lock().unwrap();
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        *entry = None;
+    }
+
+    /// Stores the configuration, returning the generation of the stored configuration.
+    pub(super) fn store(&self, configuration: C) -> usize {
+        let mut entry = self.entry.lock().unwrap();
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        *entry = Some(CacheEntry {
+            configuration: configuration,
+            cached_at: Instant::now(),
+        });
+
+        generation
+    }
+
+    /// Clears the cached configuration unless it has been replaced or cleared since the generation.
+    pub(super) fn clear_if_generation(&self, generation: usize) {
+        let mut entry = self.entry.lock().unwrap();
+        if self.generation.load(Ordering::SeqCst) == generation {
+            self.generation.fetch_add(1, Ordering::SeqCst);
+            *entry = None;
+        }
+    }
+
+    /// Stores the configuration unless the entry has been replaced or cleared since the generation.
+    fn store_if_generation(&self, generation: usize, configuration: C) {
+        let mut entry = self.entry.lock().unwrap();
+        if self.generation.load(Ordering::SeqCst) == generation {
+            *entry = Some(CacheEntry {
+                configuration: configuration,
+                cached_at: Instant::now(),
+            });
+        }
+    }
+
+    pub(super) fn cached_at(&self) -> Option<Instant> {
+        self.entry.lock().unwrap().as_ref().map(|entry| entry.cached_at)
+    }
+
+    /// Gets the cached configuration and its age.
+    pub(super) fn cached(&self) -> Option<(C, Duration)>
+        where C: Clone
+    {
+        self.entry
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|entry| (entry.configuration.clone(), entry.cached_at.elapsed()))
+    }
+}
+
+impl<R> CacheState<R::Configuration, R>
+    where R: ConfigurationReader,
+          R::Configuration: Clone + Sync,
+          R::Error: Sync
+{
+    /// Reads the configuration from the reader, caching it when the read succeeds.
+    ///
+    /// When the configuration is already being read the read in flight is shared.
+    pub(super) fn refresh(state: &Arc<Self>) -> BoxFuture<R::Configuration, SharedError<R::Error>>
+        where R: Send + Sync + 'static
+    {
+        let cache = state.clone();
+
+        state.refresh.run(move || {
+            let generation = state.generation.load(Ordering::SeqCst);
+
+            state.reader
+                .read_configuration()
+                .map(move |configuration| {
+                    cache.store_if_generation(generation, configuration.clone());
+                    configuration
+                })
+                .boxed()
+        })
+    }
+
+    /// Reads the configuration from the reader without sharing a read in flight, caching it when the read
+    /// succeeds. A read in flight started before this is not cached.
+    pub(super) fn force_refresh(state: &Arc<Self>) -> BoxFuture<R::Configuration, R::Error>
+        where R: Send + Sync + 'static
+    {
+        let cache = state.clone();
+        let generation = state.generation.fetch_add(1, Ordering::SeqCst) + 1;
+
+        state.reader
+            .read_configuration()
+            .map(move |configuration| {
+                cache.store_if_generation(generation, configuration.clone());
+                configuration
+            })
+            .boxed()
+    }
+
+    /// Refreshes the configuration unless it is already being read, returning a future which polls the refresh
+    /// once and then completes without waiting for it.
+    ///
+    /// The refresh is shared, so the reads which follow keep driving it until it completes.
+    pub(super) fn revalidate(state: &Arc<Self>) -> BoxFuture<(), ()>
+        where R: Send + Sync + 'static
+    {
+        Self::refresh(state)
+            .then(|_| Ok::<(), ()>(()))
+            .select(ok(()))
+            .then(|_| Ok(()))
+            .boxed()
+    }
+}
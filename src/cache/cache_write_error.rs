@@ -0,0 +1,35 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The error of writing through a `CacheConfigurationAccessor`, either the writer failed or it panicked while
+/// writing behind.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CacheWriteError<W> {
+    WriteError(W),
+    WriteBehindPanicked,
+}
+
+impl<W: Display> Display for CacheWriteError<W> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            CacheWriteError::WriteError(ref err) => write!(f, "Write Error {}", err),
+            CacheWriteError::WriteBehindPanicked => write!(f, "The writer panicked while writing behind"),
+        }
+    }
+}
+
+impl<W: Error> Error for CacheWriteError<W> {
+    fn description(&self) -> &str {
+        match *self {
+            CacheWriteError::WriteError(ref err) => err.description(),
+            CacheWriteError::WriteBehindPanicked => "the writer panicked while writing behind",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            CacheWriteError::WriteError(ref err) => Some(err),
+            CacheWriteError::WriteBehindPanicked => None,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+/// How a `CacheConfigurationAccessor` keeps its cache consistent with what is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheWriteMode {
+    /// Writes to the writer and caches the configuration once the write succeeds.
+    WriteThrough,
+    /// Writes to the writer and clears the cache once the write succeeds, the next read reads the
+    /// configuration again.
+    Invalidate,
+    /// Caches the configuration immediately and writes to the writer on a background thread, clearing the
+    /// cache if the write fails and the configuration is still cached.
+    ///
+    /// Writes are written one at a time in the order they were made. The write completes once the configuration
+    /// is written, failing with the error of the writer, but it does not have to be waited on.
+    WriteBehind,
+}
+
+impl Default for CacheWriteMode {
+    fn default() -> Self {
+        CacheWriteMode::WriteThrough
+    }
+}
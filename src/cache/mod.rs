@@ -1,2 +1,21 @@
+mod cache_policy;
+pub use self::cache_policy::*;
+
+mod cache_write_mode;
+pub use self::cache_write_mode::*;
+
+mod cache_write_error;
+pub use self::cache_write_error::*;
+
+mod cache_state;
+
+mod write_behind_queue;
+
+mod cache_handle;
+pub use self::cache_handle::*;
+
 mod cache_configuration_reader;
-pub use self::cache_configuration_reader::*;
\ No newline at end of file
+pub use self::cache_configuration_reader::*;
+
+mod cache_configuration_accessor;
+pub use self::cache_configuration_accessor::*;
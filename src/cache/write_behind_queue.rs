@@ -0,0 +1,42 @@
+use futures::{BoxFuture, Future, Stream};
+use futures::sync::mpsc::{self, UnboundedSender};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::panic::AssertUnwindSafe;
+use std::thread;
+
+/// Writes configurations on a background thread, one at a time in the order they were queued.
+pub(super) struct WriteBehindQueue {
+    writes: UnboundedSender<BoxFuture<(), ()>>,
+}
+
+impl WriteBehindQueue {
+    /// Starts the thread which writes the queue, it stops once the queue is dropped.
+    pub(super) fn new() -> Self {
+        let (writes, queued) = mpsc::unbounded::<BoxFuture<(), ()>>();
+        thread::spawn(move || {
+            queued.for_each(|write| {
+                    // A write which panics does not stop the writes queued after it
+                    AssertUnwindSafe(write).catch_unwind().then(|_| Ok(()))
+                })
+                .wait()
+        });
+
+        Self { writes: writes }
+    }
+
+    pub(super) fn push(&mut self, write: BoxFuture<(), ()>) {
+        // Should the thread have stopped, a new thread is started to write the queue
+        if let Err(stopped) = self.writes.unbounded_send(write) {
+            *self = Self::new();
+            self.writes
+                .unbounded_send(stopped.into_inner())
+                .expect("the write behind thread stopped as soon as it was started");
+        }
+    }
+}
+
+impl Debug for WriteBehindQueue {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("WriteBehindQueue").finish()
+    }
+}
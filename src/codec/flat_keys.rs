@@ -0,0 +1,34 @@
+use value::ConfigurationValue;
+
+/// Flattens the nested tables of a value into dot separated keys, joining arrays of text into a comma separated string.
+///
+/// Returns the key of the first value which cannot be flattened, such as an array containing a table.
+pub(super) fn flatten(prefix: &str, value: &ConfigurationValue, flattened: &mut Vec<(String, String)>) -> Result<(), String> {
+    match *value {
+        ConfigurationValue::String(ref value) => flattened.push((prefix.to_owned(), value.clone())),
+        ConfigurationValue::Array(ref values) => {
+            let mut joined = Vec::with_capacity(values.len());
+            for value in values {
+                match value.as_str() {
+                    Some(value) => joined.push(value),
+                    None => return Err(prefix.to_owned()),
+                }
+            }
+
+            flattened.push((prefix.to_owned(), joined.join(",")));
+        }
+        ConfigurationValue::Table(ref table) => {
+            for (key, value) in table {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+
+                flatten(&key, value, flattened)?;
+            }
+        }
+    }
+
+    Ok(())
+}
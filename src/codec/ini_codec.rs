@@ -0,0 +1,228 @@
+use ConfigurationCodec;
+use super::IniCodecError;
+use super::flat_keys::flatten;
+use value::ConfigurationValue;
+use std::str;
+
+/// A `ConfigurationCodec` which converts a `ConfigurationValue` to and from INI.
+///
+/// Keys before the first section are top level keys, and the keys of each `[section]` are nested within a
+/// table named after the section. Keys containing `.` are nested tables within the section, as they are
+/// encoded. Lines starting with `;` or `#` are comments, and a value may be surrounded
+/// by quotes to keep leading or trailing whitespace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IniCodec;
+
+impl IniCodec {
+    /// Creates a new `IniCodec`.
+    pub fn new() -> Self {
+        IniCodec
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    let quoted = value.len() >= 2 &&
+                 ((value.starts_with('"') && value.ends_with('"')) || (value.starts_with('\'') && value.ends_with('\'')));
+
+    if quoted { &value[1..value.len() - 1] } else { value }
+}
+
+fn quote(key: &str, value: &str) -> Result<String, IniCodecError> {
+    if value.contains('\n') || value.contains('\r') {
+        return Err(IniCodecError::Unsupported(key.to_owned()));
+    }
+
+    let needs_quotes = value.trim() != value || value.starts_with(';') || value.starts_with('#') ||
+                       value.starts_with('"') || value.starts_with('\'');
+
+    Ok(if needs_quotes { format!("\"{}\"", value) } else { value.to_owned() })
+}
+
+impl ConfigurationCodec<ConfigurationValue> for IniCodec {
+    type Error = IniCodecError;
+
+    fn decode(&self, bytes: &[u8]) -> Result<ConfigurationValue, Self::Error> {
+        let text = str::from_utf8(bytes).map_err(|_| IniCodecError::Utf8)?;
+
+        let mut configuration = ConfigurationValue::table();
+        let mut section: Option<String> = None;
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with('[') {
+                if !line.ends_with(']') {
+                    return Err(IniCodecError::Syntax(index + 1, "unterminated section".to_owned()));
+                }
+
+                let name = line[1..line.len() - 1].trim();
+                if name.is_empty() {
+                    return Err(IniCodecError::Syntax(index + 1, "empty section name".to_owned()));
+                }
+
+                // A section which is repeated continues the existing section
+                if configuration.get(&[name]).and_then(ConfigurationValue::as_table).is_none() {
+                    configuration.try_insert(&[name], ConfigurationValue::table())
+                        .map_err(|_| IniCodecError::Syntax(index + 1, format!("section '{}' conflicts with a key", name)))?;
+                }
+
+                section = Some(name.to_owned());
+                continue;
+            }
+
+            let separator = match line.find(|c| c == '=' || c == ':') {
+                Some(separator) => separator,
+                None => return Err(IniCodecError::Syntax(index + 1, "expected a key and value".to_owned())),
+            };
+
+            let key = line[..separator].trim();
+            if key.is_empty() {
+                return Err(IniCodecError::Syntax(index + 1, "empty key".to_owned()));
+            }
+
+            let mut path: Vec<&str> = section.iter().map(String::as_str).collect();
+            path.extend(key.split('.'));
+            if path.iter().any(|key| key.is_empty()) {
+                return Err(IniCodecError::Syntax(index + 1, format!("invalid key '{}'", key)));
+            }
+
+            let value = ConfigurationValue::from(unquote(line[separator + 1..].trim()));
+            configuration.try_insert(&path, value)
+                .map_err(|err| IniCodecError::Syntax(index + 1, format!("key '{}' conflicts with '{}'", key, err.path().join("."))))?;
+        }
+
+        Ok(configuration)
+    }
+
+    fn encode(&self, configuration: &ConfigurationValue) -> Result<Vec<u8>, Self::Error> {
+        let table = match configuration.as_table() {
+            Some(table) => table,
+            None => return Err(IniCodecError::Unsupported(String::new())),
+        };
+
+        let mut globals = Vec::new();
+        let mut sections = Vec::new();
+        for (key, value) in table {
+            match *value {
+                ConfigurationValue::Table(_) => sections.push((key, value)),
+                _ => flatten(key, value, &mut globals).map_err(IniCodecError::Unsupported)?,
+            }
+        }
+
+        let mut encoded = String::new();
+        for (key, value) in globals {
+            encoded.push_str(&format!("{} = {}\n", key, quote(&key, &value)?));
+        }
+
+        for (name, section) in sections {
+            if !encoded.is_empty() {
+                encoded.push('\n');
+            }
+
+            encoded.push_str(&format!("[{}]\n", name));
+
+            let mut entries = Vec::new();
+            flatten("", section, &mut entries).map_err(|key| IniCodecError::Unsupported(format!("{}.{}", name, key)))?;
+            for (key, value) in entries {
+                encoded.push_str(&format!("{} = {}\n", key, quote(&key, &value)?));
+            }
+        }
+
+        Ok(encoded.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ConfigurationCodec;
+    use codec::{IniCodec, IniCodecError};
+    use value::ConfigurationValue;
+
+    const INI: &'static str = "; global settings
+name = legacy tool
+
+[server]
+# where to listen
+host = localhost
+port: 8080
+banner = \"  welcome  \"
+";
+
+    #[test]
+    fn decode_maps_sections_to_tables() {
+        // Act
+        let configuration = IniCodec::new().decode(INI.as_bytes()).unwrap();
+
+        // Assert
+        assert_eq!(configuration.get(&["name"]), Some(&ConfigurationValue::from("legacy tool")));
+        assert_eq!(configuration.get(&["server", "port"]), Some(&ConfigurationValue::from("8080")));
+        assert_eq!(configuration.get(&["server", "banner"]), Some(&ConfigurationValue::from("  welcome  ")));
+    }
+
+    #[test]
+    fn decode_repeated_section_keeps_earlier_keys() {
+        // Arrange
+        let ini = b"[server]\nhost = a\n[other]\nx = 1\n[server]\nport = 2\n";
+
+        // Act
+        let configuration = IniCodec::new().decode(ini).unwrap();
+
+        // Assert
+        assert_eq!(configuration.get(&["server", "host"]), Some(&ConfigurationValue::from("a")));
+        assert_eq!(configuration.get(&["server", "port"]), Some(&ConfigurationValue::from("2")));
+        assert_eq!(configuration.get(&["other", "x"]), Some(&ConfigurationValue::from("1")));
+    }
+
+    #[test]
+    fn decode_line_without_value_returns_syntax_error() {
+        // Arrange
+        let ini = b"[server]\nhost\n";
+
+        // Act
+        let error = IniCodec::new().decode(ini).unwrap_err();
+
+        // Assert
+        assert_eq!(error, IniCodecError::Syntax(2, "expected a key and value".to_owned()));
+    }
+
+    #[test]
+    fn encode_round_trips_configuration() {
+        // Arrange
+        let configuration = IniCodec::new().decode(INI.as_bytes()).unwrap();
+
+        // Act
+        let encoded = IniCodec::new().encode(&configuration).unwrap();
+
+        // Assert
+        assert_eq!(IniCodec::new().decode(&encoded).unwrap(), configuration);
+    }
+
+    #[test]
+    fn encode_nested_tables_round_trips_configuration() {
+        // Arrange
+        let mut configuration = ConfigurationValue::table();
+        configuration.insert(&["server", "tls", "certificate"], "server.pem".into());
+        configuration.insert(&["server", "port"], "8080".into());
+
+        // Act
+        let encoded = IniCodec::new().encode(&configuration).unwrap();
+
+        // Assert
+        assert_eq!(IniCodec::new().decode(&encoded).unwrap(), configuration);
+    }
+
+    #[test]
+    fn decode_value_and_table_with_same_key_returns_syntax_error() {
+        // Arrange
+        let ini = b"[server]\ntls = on\ntls.certificate = server.pem\n";
+
+        // Act
+        let error = IniCodec::new().decode(ini).unwrap_err();
+
+        // Assert
+        assert_eq!(error, IniCodecError::Syntax(3, "key 'tls.certificate' conflicts with 'server.tls'".to_owned()));
+    }
+}
@@ -0,0 +1,16 @@
+quick_error! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum IniCodecError {
+        Utf8 {
+            description("ini configuration is not valid utf-8")
+        }
+        Syntax(line: usize, message: String) {
+            description("invalid ini configuration")
+            display("invalid ini configuration at line {}: {}", line, message)
+        }
+        Unsupported(key: String) {
+            description("value cannot be represented in ini")
+            display("the value of '{}' cannot be represented in ini", key)
+        }
+    }
+}
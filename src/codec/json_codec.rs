@@ -0,0 +1,103 @@
+use ConfigurationCodec;
+use super::JsonCodecError;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json;
+
+/// A `ConfigurationCodec` which converts any serde compatible configuration to and from JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonCodec {
+    pretty: bool,
+}
+
+impl JsonCodec {
+    /// Creates a new `JsonCodec` which encodes compact JSON.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `JsonCodec` which encodes indented JSON.
+    pub fn pretty() -> Self {
+        Self { pretty: true }
+    }
+}
+
+impl<C> ConfigurationCodec<C> for JsonCodec
+    where C: Serialize + DeserializeOwned
+{
+    type Error = JsonCodecError;
+
+    fn decode(&self, bytes: &[u8]) -> Result<C, Self::Error> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+
+    fn encode(&self, configuration: &C) -> Result<Vec<u8>, Self::Error> {
+        let bytes = if self.pretty {
+            serde_json::to_vec_pretty(configuration)?
+        } else {
+            serde_json::to_vec(configuration)?
+        };
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationCodec, ConfigurationReader, ConfigurationWriter};
+    use codec::JsonCodec;
+    use file::FileConfigurationAccessor;
+    use futures::Future;
+    use tempdir::TempDir;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct TestConfiguration {
+        name: String,
+        port: u16,
+    }
+
+    #[test]
+    fn decode_invalid_json_returns_position() {
+        // Arrange
+        let json = b"{\n  \"name\": \"test\",\n  \"port\": }";
+
+        // Act
+        let error = ConfigurationCodec::<TestConfiguration>::decode(&JsonCodec::new(), json).unwrap_err();
+
+        // Assert
+        assert!(error.is_syntax());
+        assert_eq!((error.line(), error.column()), (3, 11));
+    }
+
+    #[test]
+    fn decode_mismatched_json_returns_data_error() {
+        // Arrange
+        let json = br#"{"name": "test", "port": "high"}"#;
+
+        // Act
+        let error = ConfigurationCodec::<TestConfiguration>::decode(&JsonCodec::new(), json).unwrap_err();
+
+        // Assert
+        assert!(error.is_data());
+        assert_eq!(error.line(), 1);
+    }
+
+    #[test]
+    fn file_accessor_round_trips_configuration() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let mut accessor = FileConfigurationAccessor::new(directory.path().join("configuration.json"), JsonCodec::pretty());
+        let configuration = TestConfiguration {
+            name: "test".to_owned(),
+            port: 8080,
+        };
+
+        accessor.write_configuration(&configuration).wait().unwrap();
+
+        // Act
+        let read_configuration = accessor.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(read_configuration, configuration);
+    }
+}
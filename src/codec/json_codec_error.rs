@@ -0,0 +1,58 @@
+use serde_json::Error as SerdeJsonError;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The error when a configuration could not be decoded from or encoded to JSON.
+#[derive(Debug)]
+pub struct JsonCodecError {
+    error: SerdeJsonError,
+}
+
+impl JsonCodecError {
+    /// Gets the one-based line at which decoding failed, this is 0 if the error did not occur while decoding.
+    pub fn line(&self) -> usize {
+        self.error.line()
+    }
+
+    /// Gets the one-based column at which decoding failed, this is 0 if the error did not occur while decoding.
+    pub fn column(&self) -> usize {
+        self.error.column()
+    }
+
+    /// Returns whether the JSON was not syntactically valid.
+    pub fn is_syntax(&self) -> bool {
+        self.error.is_syntax()
+    }
+
+    /// Returns whether the JSON was valid but did not match the type of the configuration.
+    pub fn is_data(&self) -> bool {
+        self.error.is_data()
+    }
+
+    /// Returns whether the JSON ended before a complete configuration was decoded.
+    pub fn is_eof(&self) -> bool {
+        self.error.is_eof()
+    }
+}
+
+impl From<SerdeJsonError> for JsonCodecError {
+    fn from(error: SerdeJsonError) -> Self {
+        Self { error: error }
+    }
+}
+
+impl Display for JsonCodecError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl Error for JsonCodecError {
+    fn description(&self) -> &str {
+        "invalid json configuration"
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        Some(&self.error)
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(feature = "json")]
+mod json_codec_error;
+#[cfg(feature = "json")]
+pub use self::json_codec_error::*;
+
+#[cfg(feature = "json")]
+mod json_codec;
+#[cfg(feature = "json")]
+pub use self::json_codec::*;
+
+#[cfg(feature = "toml")]
+mod toml_codec_error;
+#[cfg(feature = "toml")]
+pub use self::toml_codec_error::*;
+
+#[cfg(feature = "toml")]
+mod toml_codec;
+#[cfg(feature = "toml")]
+pub use self::toml_codec::*;
+
+#[cfg(feature = "yaml")]
+mod yaml_codec_error;
+#[cfg(feature = "yaml")]
+pub use self::yaml_codec_error::*;
+
+#[cfg(feature = "yaml")]
+mod yaml_document_selector;
+#[cfg(feature = "yaml")]
+pub use self::yaml_document_selector::*;
+
+#[cfg(feature = "yaml")]
+mod yaml_codec;
+#[cfg(feature = "yaml")]
+pub use self::yaml_codec::*;
+
+mod flat_keys;
+
+mod ini_codec_error;
+pub use self::ini_codec_error::*;
+
+mod ini_codec;
+pub use self::ini_codec::*;
+
+mod properties_codec_error;
+pub use self::properties_codec_error::*;
+
+mod properties_codec;
+pub use self::properties_codec::*;
@@ -0,0 +1,296 @@
+use ConfigurationCodec;
+use super::PropertiesCodecError;
+use super::flat_keys::flatten;
+use value::ConfigurationValue;
+use std::char;
+use std::str::{self, Chars};
+use std::iter::Peekable;
+
+/// A `ConfigurationCodec` which converts a `ConfigurationValue` to and from Java `.properties`.
+///
+/// Keys are split on `.` into nested keys, so `server.port=8080` maps to the key `port` within the table
+/// `server`. Escapes, `\uXXXX` unicode escapes and line continuations are supported. Files are read as
+/// UTF-8 falling back to ISO-8859-1, and written as ASCII with every other character escaped so either
+/// encoding can read them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PropertiesCodec;
+
+impl PropertiesCodec {
+    /// Creates a new `PropertiesCodec`.
+    pub fn new() -> Self {
+        PropertiesCodec
+    }
+}
+
+fn decode_text(bytes: &[u8]) -> String {
+    match str::from_utf8(bytes) {
+        Ok(text) => text.to_owned(),
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|&c| c == '\\').count() % 2 == 1
+}
+
+/// Joins physical lines ending in an unescaped backslash into logical lines, returning each with its starting line number.
+fn logical_lines(text: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+
+    for (index, line) in text.lines().enumerate() {
+        let line = match current {
+            Some(_) => line.trim_start(),
+            None => {
+                let trimmed = line.trim_start();
+                if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('!') {
+                    continue;
+                }
+
+                trimmed
+            }
+        };
+
+        let continues = ends_with_continuation(line);
+        let content = if continues { &line[..line.len() - 1] } else { line };
+
+        let (start, mut logical) = current.take().unwrap_or_else(|| (index + 1, String::new()));
+        logical.push_str(content);
+
+        if continues {
+            current = Some((start, logical));
+        } else {
+            lines.push((start, logical));
+        }
+    }
+
+    if let Some(last) = current {
+        lines.push(last);
+    }
+
+    lines
+}
+
+fn unescape_char(chars: &mut Peekable<Chars>, line: usize) -> Result<char, PropertiesCodecError> {
+    let escaped = match chars.next() {
+        Some(escaped) => escaped,
+        None => return Ok('\\'),
+    };
+
+    let unescaped = match escaped {
+        't' => '\t',
+        'n' => '\n',
+        'r' => '\r',
+        'f' => '\u{c}',
+        'u' => {
+            let first = read_code_unit(chars, line)?;
+            if (0xD800..0xDC00).contains(&first) {
+                // A high surrogate must be followed by an escaped low surrogate
+                if chars.next() != Some('\\') || chars.next() != Some('u') {
+                    return Err(PropertiesCodecError::Syntax(line, "unpaired surrogate".to_owned()));
+                }
+
+                let second = read_code_unit(chars, line)?;
+                let decoded = char::decode_utf16(vec![first, second]).next();
+                match decoded {
+                    Some(Ok(c)) => c,
+                    _ => return Err(PropertiesCodecError::Syntax(line, "unpaired surrogate".to_owned())),
+                }
+            } else {
+                match char::from_u32(first as u32) {
+                    Some(c) => c,
+                    None => return Err(PropertiesCodecError::Syntax(line, "unpaired surrogate".to_owned())),
+                }
+            }
+        }
+        other => other,
+    };
+
+    Ok(unescaped)
+}
+
+fn read_code_unit(chars: &mut Peekable<Chars>, line: usize) -> Result<u16, PropertiesCodecError> {
+    let digits: String = chars.by_ref().take(4).collect();
+    if digits.len() != 4 {
+        return Err(PropertiesCodecError::Syntax(line, "truncated unicode escape".to_owned()));
+    }
+
+    u16::from_str_radix(&digits, 16)
+        .map_err(|_| PropertiesCodecError::Syntax(line, format!("invalid unicode escape '\\u{}'", digits)))
+}
+
+fn parse_line(line: &str, number: usize) -> Result<(String, String), PropertiesCodecError> {
+    let mut chars = line.chars().peekable();
+
+    let mut key = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => key.push(unescape_char(&mut chars, number)?),
+            '=' | ':' => break,
+            c if c.is_whitespace() => {
+                // Whitespace may be followed by a single separator
+                while chars.peek().map_or(false, |c| c.is_whitespace()) {
+                    chars.next();
+                }
+
+                if chars.peek() == Some(&'=') || chars.peek() == Some(&':') {
+                    chars.next();
+                }
+
+                break;
+            }
+            c => key.push(c),
+        }
+    }
+
+    while chars.peek().map_or(false, |c| c.is_whitespace()) {
+        chars.next();
+    }
+
+    let mut value = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.push(unescape_char(&mut chars, number)?),
+            c => value.push(c),
+        }
+    }
+
+    Ok((key, value))
+}
+
+fn escape(text: &str, is_key: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for (index, c) in text.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\u{c}' => escaped.push_str("\\f"),
+            '=' | ':' | '#' | '!' if is_key || index == 0 => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' ' if is_key || index == 0 => escaped.push_str("\\ "),
+            c if c.is_ascii() && !c.is_ascii_control() => escaped.push(c),
+            c => {
+                let mut units = [0; 2];
+                for unit in c.encode_utf16(&mut units).iter() {
+                    escaped.push_str(&format!("\\u{:04X}", unit));
+                }
+            }
+        }
+    }
+
+    escaped
+}
+
+impl ConfigurationCodec<ConfigurationValue> for PropertiesCodec {
+    type Error = PropertiesCodecError;
+
+    fn decode(&self, bytes: &[u8]) -> Result<ConfigurationValue, Self::Error> {
+        let text = decode_text(bytes);
+
+        let mut configuration = ConfigurationValue::table();
+        for (number, line) in logical_lines(&text) {
+            let (key, value) = parse_line(&line, number)?;
+
+            let path: Vec<_> = key.split('.').collect();
+            if path.iter().any(|key| key.is_empty()) {
+                return Err(PropertiesCodecError::Syntax(number, format!("invalid key '{}'", key)));
+            }
+
+            configuration.try_insert(&path, ConfigurationValue::String(value))
+                .map_err(|err| PropertiesCodecError::Syntax(number, format!("key '{}' conflicts with '{}'", key, err.path().join("."))))?;
+        }
+
+        Ok(configuration)
+    }
+
+    fn encode(&self, configuration: &ConfigurationValue) -> Result<Vec<u8>, Self::Error> {
+        let mut entries = Vec::new();
+        flatten("", configuration, &mut entries).map_err(PropertiesCodecError::Unsupported)?;
+
+        let mut encoded = String::new();
+        for (key, value) in entries {
+            if key.is_empty() {
+                return Err(PropertiesCodecError::Unsupported(key));
+            }
+
+            encoded.push_str(&format!("{}={}\n", escape(&key, true), escape(&value, false)));
+        }
+
+        Ok(encoded.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ConfigurationCodec;
+    use codec::{PropertiesCodec, PropertiesCodecError};
+    use value::ConfigurationValue;
+
+    const PROPERTIES: &'static str = "# database settings
+! another comment
+database.url = jdbc:postgresql://localhost/app
+database.pool\\ size : 4
+greeting = caf\\u00e9 \\
+           au lait
+tab\tvalue
+";
+
+    #[test]
+    fn decode_handles_escapes_and_continuations() {
+        // Act
+        let configuration = PropertiesCodec::new().decode(PROPERTIES.as_bytes()).unwrap();
+
+        // Assert
+        assert_eq!(configuration.get(&["database", "url"]),
+                   Some(&ConfigurationValue::from("jdbc:postgresql://localhost/app")));
+        assert_eq!(configuration.get(&["database", "pool size"]), Some(&ConfigurationValue::from("4")));
+        assert_eq!(configuration.get(&["greeting"]), Some(&ConfigurationValue::from("café au lait")));
+        assert_eq!(configuration.get(&["tab"]), Some(&ConfigurationValue::from("value")));
+    }
+
+    #[test]
+    fn decode_latin1_falls_back_from_utf8() {
+        // Arrange
+        let properties = b"name=caf\xe9";
+
+        // Act
+        let configuration = PropertiesCodec::new().decode(properties).unwrap();
+
+        // Assert
+        assert_eq!(configuration.get(&["name"]), Some(&ConfigurationValue::from("café")));
+    }
+
+    #[test]
+    fn encode_round_trips_configuration() {
+        // Arrange
+        let mut configuration = ConfigurationValue::table();
+        configuration.insert(&["key with spaces"], " leading=space ".into());
+        configuration.insert(&["unicode", "emoji"], "\u{1F600} and é".into());
+        configuration.insert(&["multi"], "first\nsecond".into());
+
+        // Act
+        let encoded = PropertiesCodec::new().encode(&configuration).unwrap();
+
+        // Assert
+        assert!(encoded.is_ascii());
+        assert_eq!(PropertiesCodec::new().decode(&encoded).unwrap(), configuration);
+    }
+
+    #[test]
+    fn decode_value_and_table_with_same_key_returns_syntax_error() {
+        // Arrange
+        let properties = b"log4j.logger=INFO\nlog4j.logger.app=DEBUG\n";
+
+        // Act
+        let error = PropertiesCodec::new().decode(properties).unwrap_err();
+
+        // Assert
+        assert_eq!(error,
+                   PropertiesCodecError::Syntax(2, "key 'log4j.logger.app' conflicts with 'log4j.logger'".to_owned()));
+    }
+}
@@ -0,0 +1,13 @@
+quick_error! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum PropertiesCodecError {
+        Syntax(line: usize, message: String) {
+            description("invalid properties configuration")
+            display("invalid properties configuration at line {}: {}", line, message)
+        }
+        Unsupported(key: String) {
+            description("value cannot be represented in properties")
+            display("the value of '{}' cannot be represented in properties", key)
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use ConfigurationCodec;
+use super::TomlCodecError;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use toml_edit::{ArrayOfTables, DocumentMut, InlineTable, Item, Table, Value};
+use toml_edit::{de, ser};
+
+/// A `ConfigurationCodec` which converts any serde compatible configuration to and from TOML.
+///
+/// When updating an existing file, the comments, key order and formatting of the file are preserved for
+/// every value which has not changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TomlCodec;
+
+impl TomlCodec {
+    /// Creates a new `TomlCodec`.
+    pub fn new() -> Self {
+        TomlCodec
+    }
+
+    fn encode_document<C: Serialize>(&self, configuration: &C) -> Result<String, TomlCodecError> {
+        ser::to_string_pretty(configuration).map_err(TomlCodecError::Encode)
+    }
+}
+
+impl<C> ConfigurationCodec<C> for TomlCodec
+    where C: Serialize + DeserializeOwned
+{
+    type Error = TomlCodecError;
+
+    fn decode(&self, bytes: &[u8]) -> Result<C, Self::Error> {
+        de::from_slice(bytes).map_err(|err| TomlCodecError::decode(err, bytes))
+    }
+
+    fn encode(&self, configuration: &C) -> Result<Vec<u8>, Self::Error> {
+        self.encode_document(configuration).map(String::into_bytes)
+    }
+
+    fn encode_update(&self, configuration: &C, existing: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        let encoded = self.encode_document(configuration)?;
+
+        let existing_document = ::std::str::from_utf8(existing)
+            .ok()
+            .and_then(|existing| existing.parse::<DocumentMut>().ok());
+
+        // An existing file which is not valid TOML has no layout worth keeping
+        let mut document = match existing_document {
+            Some(document) => document,
+            None => return Ok(encoded.into_bytes()),
+        };
+
+        let updated = encoded.parse::<DocumentMut>()
+            .expect("the serialized configuration should be valid toml");
+        update_table(document.as_table_mut(), updated.into_table());
+
+        Ok(document.to_string().into_bytes())
+    }
+}
+
+fn update_item(existing: &mut Item, updated: Item) {
+    match (existing, updated) {
+        (&mut Item::Table(ref mut existing), Item::Table(updated)) => update_table(existing, updated),
+        (&mut Item::Table(ref mut existing), Item::Value(Value::InlineTable(updated))) => {
+            update_table(existing, updated.into_table())
+        }
+        (&mut Item::Value(Value::InlineTable(ref mut existing)), Item::Table(updated)) => {
+            update_inline_table(existing, updated.into_inline_table())
+        }
+        (&mut Item::ArrayOfTables(ref mut existing), Item::ArrayOfTables(updated)) => {
+            update_array_of_tables(existing, updated)
+        }
+        (&mut Item::Value(ref mut existing), Item::Value(updated)) => update_value(existing, updated),
+        (existing, updated) => *existing = updated,
+    }
+}
+
+fn update_table(existing: &mut Table, updated: Table) {
+    existing.retain(|key, _| updated.contains_key(key));
+
+    for (key, item) in updated {
+        // Borrowing the existing item in the condition would keep `existing` borrowed for the insert
+        if existing.contains_key(&key) {
+            update_item(existing.get_mut(&key).unwrap(), item);
+        } else {
+            existing.insert(&key, item);
+        }
+    }
+}
+
+fn update_inline_table(existing: &mut InlineTable, updated: InlineTable) {
+    existing.retain(|key, _| updated.contains_key(key));
+
+    for (key, value) in updated {
+        if existing.contains_key(&key) {
+            update_value(existing.get_mut(&key).unwrap(), value);
+        } else {
+            existing.insert(key, value);
+        }
+    }
+}
+
+fn update_array_of_tables(existing: &mut ArrayOfTables, updated: ArrayOfTables) {
+    while existing.len() > updated.len() {
+        let last = existing.len() - 1;
+        existing.remove(last);
+    }
+
+    for (index, table) in updated.into_iter().enumerate() {
+        if index < existing.len() {
+            update_table(existing.get_mut(index).unwrap(), table);
+        } else {
+            existing.push(table);
+        }
+    }
+}
+
+fn update_value(existing: &mut Value, updated: Value) {
+    match (existing, updated) {
+        (&mut Value::InlineTable(ref mut existing), Value::InlineTable(updated)) => {
+            update_inline_table(existing, updated)
+        }
+        (existing, updated) => {
+            if !value_eq(existing, &updated) {
+                // Keep the whitespace and comments surrounding the value
+                let decor = existing.decor().clone();
+                *existing = updated;
+                *existing.decor_mut() = decor;
+            }
+        }
+    }
+}
+
+fn value_eq(first: &Value, second: &Value) -> bool {
+    match (first, second) {
+        (&Value::String(ref first), &Value::String(ref second)) => first.value() == second.value(),
+        (&Value::Integer(ref first), &Value::Integer(ref second)) => first.value() == second.value(),
+        (&Value::Float(ref first), &Value::Float(ref second)) => {
+            let (first, second) = (first.value(), second.value());
+            first == second || (first.is_nan() && second.is_nan())
+        }
+        (&Value::Boolean(ref first), &Value::Boolean(ref second)) => first.value() == second.value(),
+        (&Value::Datetime(ref first), &Value::Datetime(ref second)) => first.value() == second.value(),
+        (&Value::Array(ref first), &Value::Array(ref second)) => {
+            first.len() == second.len() && first.iter().zip(second.iter()).all(|(first, second)| value_eq(first, second))
+        }
+        (&Value::InlineTable(ref first), &Value::InlineTable(ref second)) => {
+            first.len() == second.len() &&
+            first.iter().all(|(key, first)| second.get(key).map_or(false, |second| value_eq(first, second)))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationCodec, ConfigurationReader, ConfigurationWriter};
+    use codec::TomlCodec;
+    use file::FileConfigurationAccessor;
+    use futures::Future;
+    use std::fs::File;
+    use std::io::{Read, Write};
+    use tempdir::TempDir;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct ServerConfiguration {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct TestConfiguration {
+        name: String,
+        server: ServerConfiguration,
+    }
+
+    const EXISTING: &'static str = r#"# The name of this instance
+name   = "test"  # aligned by hand
+
+[server]
+# Where to listen
+port = 0x1F90
+host = "localhost"
+"#;
+
+    #[test]
+    fn decode_invalid_toml_returns_position() {
+        // Arrange
+        let toml = b"name = \"test\"\n[server]\nport = = 1\n";
+
+        // Act
+        let error = ConfigurationCodec::<TestConfiguration>::decode(&TomlCodec::new(), toml).unwrap_err();
+
+        // Assert
+        assert_eq!(error.line(), 3);
+    }
+
+    #[test]
+    fn encode_update_unchanged_configuration_preserves_file() {
+        // Arrange
+        let configuration: TestConfiguration = TomlCodec::new().decode(EXISTING.as_bytes()).unwrap();
+
+        // Act
+        let encoded = TomlCodec::new().encode_update(&configuration, EXISTING.as_bytes()).unwrap();
+
+        // Assert
+        assert_eq!(String::from_utf8(encoded).unwrap(), EXISTING);
+    }
+
+    #[test]
+    fn encode_update_changed_value_preserves_comments() {
+        // Arrange
+        let mut configuration: TestConfiguration = TomlCodec::new().decode(EXISTING.as_bytes()).unwrap();
+        configuration.server.host = "example.com".to_owned();
+
+        // Act
+        let encoded = TomlCodec::new().encode_update(&configuration, EXISTING.as_bytes()).unwrap();
+
+        // Assert
+        assert_eq!(String::from_utf8(encoded).unwrap(), EXISTING.replace("localhost", "example.com"));
+    }
+
+    #[test]
+    fn file_accessor_write_preserves_comments() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let path = directory.path().join("configuration.toml");
+        File::create(&path).unwrap().write_all(EXISTING.as_bytes()).unwrap();
+
+        let mut accessor = FileConfigurationAccessor::<TestConfiguration, _>::new(path.clone(), TomlCodec::new());
+        let mut configuration = accessor.read_configuration().wait().unwrap();
+        configuration.name = "renamed".to_owned();
+
+        // Act
+        accessor.write_configuration(&configuration).wait().unwrap();
+
+        // Assert
+        let mut contents = String::new();
+        File::open(&path).unwrap().read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, EXISTING.replace("\"test\"", "\"renamed\""));
+    }
+}
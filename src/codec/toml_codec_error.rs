@@ -0,0 +1,79 @@
+use toml_edit::de::Error as DeError;
+use toml_edit::ser::Error as SerError;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The error when a configuration could not be decoded from or encoded to TOML.
+#[derive(Debug)]
+pub enum TomlCodecError {
+    Decode {
+        error: DeError,
+        line: usize,
+        column: usize,
+    },
+    Encode(SerError),
+}
+
+impl TomlCodecError {
+    pub(super) fn decode(error: DeError, bytes: &[u8]) -> Self {
+        let (line, column) = match error.span() {
+            Some(span) => {
+                let preceding = &bytes[..span.start.min(bytes.len())];
+                let line_start = preceding.iter().rposition(|&b| b == b'\n').map_or(0, |index| index + 1);
+                let line = preceding.iter().filter(|&&b| b == b'\n').count() + 1;
+
+                (line, preceding.len() - line_start + 1)
+            }
+            None => (0, 0),
+        };
+
+        TomlCodecError::Decode {
+            error: error,
+            line: line,
+            column: column,
+        }
+    }
+
+    /// Gets the one-based line at which decoding failed, this is 0 if the position is not known.
+    pub fn line(&self) -> usize {
+        match *self {
+            TomlCodecError::Decode { line, .. } => line,
+            TomlCodecError::Encode(_) => 0,
+        }
+    }
+
+    /// Gets the one-based column at which decoding failed, this is 0 if the position is not known.
+    pub fn column(&self) -> usize {
+        match *self {
+            TomlCodecError::Decode { column, .. } => column,
+            TomlCodecError::Encode(_) => 0,
+        }
+    }
+}
+
+impl Display for TomlCodecError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            TomlCodecError::Decode { ref error, line, column } => {
+                write!(f, "{} at line {} column {}", error.message(), line, column)
+            }
+            TomlCodecError::Encode(ref error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for TomlCodecError {
+    fn description(&self) -> &str {
+        match *self {
+            TomlCodecError::Decode { .. } => "invalid toml configuration",
+            TomlCodecError::Encode(_) => "configuration could not be encoded as toml",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            TomlCodecError::Decode { ref error, .. } => Some(error),
+            TomlCodecError::Encode(ref error) => Some(error),
+        }
+    }
+}
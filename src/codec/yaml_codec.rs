@@ -0,0 +1,237 @@
+use ConfigurationCodec;
+use super::{YamlCodecError, YamlDocumentSelector};
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use serde_yaml::{self, Deserializer, Value};
+
+/// A `ConfigurationCodec` which converts any serde compatible configuration to and from YAML.
+///
+/// Anchors, aliases and `<<` merge keys are resolved before the configuration is decoded. By default the
+/// bytes must contain a single document, a `YamlDocumentSelector` can be used to pick one document out of
+/// a stream of documents.
+///
+/// Errors in the YAML syntax report their position, errors where the YAML does not match the type of the
+/// configuration do not as they are detected after merge keys have been resolved.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct YamlCodec {
+    selector: Option<YamlDocumentSelector>,
+}
+
+impl YamlCodec {
+    /// Creates a new `YamlCodec` for a single YAML document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `YamlCodec` which decodes the document chosen by the selector from a stream of YAML documents.
+    ///
+    /// When encoding over an existing stream only the selected document is replaced.
+    pub fn selecting(selector: YamlDocumentSelector) -> Self {
+        Self { selector: Some(selector) }
+    }
+
+    pub fn selector(&self) -> Option<&YamlDocumentSelector> {
+        self.selector.as_ref()
+    }
+
+    fn documents(bytes: &[u8]) -> Result<Vec<Value>, YamlCodecError> {
+        let mut documents = Vec::new();
+        for document in Deserializer::from_slice(bytes) {
+            documents.push(Value::deserialize(document)?);
+        }
+
+        Ok(documents)
+    }
+
+    fn merged(mut document: Value) -> Result<Value, YamlCodecError> {
+        document.apply_merge()?;
+        Ok(document)
+    }
+
+    fn selected_index(selector: &YamlDocumentSelector, documents: &[Value]) -> Result<Option<usize>, YamlCodecError> {
+        for (index, document) in documents.iter().enumerate() {
+            if selector.matches(index, &Self::merged(document.clone())?) {
+                return Ok(Some(index));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl<C> ConfigurationCodec<C> for YamlCodec
+    where C: Serialize + DeserializeOwned
+{
+    type Error = YamlCodecError;
+
+    fn decode(&self, bytes: &[u8]) -> Result<C, Self::Error> {
+        let document = match self.selector {
+            None => serde_yaml::from_slice(bytes)?,
+            Some(ref selector) => {
+                let mut documents = Self::documents(bytes)?;
+                match Self::selected_index(selector, &documents)? {
+                    Some(index) => documents.swap_remove(index),
+                    None => return Err(YamlCodecError::NoDocument),
+                }
+            }
+        };
+
+        Ok(serde_yaml::from_value(Self::merged(document)?)?)
+    }
+
+    fn encode(&self, configuration: &C) -> Result<Vec<u8>, Self::Error> {
+        Ok(serde_yaml::to_string(configuration)?.into_bytes())
+    }
+
+    fn encode_update(&self, configuration: &C, existing: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        let selector = match self.selector {
+            Some(ref selector) => selector,
+            None => return self.encode(configuration),
+        };
+
+        // The other documents in the stream are kept, a stream which cannot be parsed is replaced
+        let mut documents = match Self::documents(existing) {
+            Ok(documents) => documents,
+            Err(_) => return self.encode(configuration),
+        };
+
+        let document = serde_yaml::to_value(configuration)?;
+        match Self::selected_index(selector, &documents)? {
+            Some(index) => documents[index] = document,
+            None => documents.push(document),
+        }
+
+        let mut encoded = String::new();
+        for document in &documents {
+            encoded.push_str("---\n");
+            encoded.push_str(&serde_yaml::to_string(document)?);
+        }
+
+        Ok(encoded.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ConfigurationCodec;
+    use codec::{YamlCodec, YamlCodecError, YamlDocumentSelector};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct ServiceConfiguration {
+        name: String,
+        image: String,
+        replicas: u32,
+    }
+
+    const SERVICES: &'static str = r#"
+defaults: &defaults
+  image: app:latest
+  replicas: 2
+web:
+  <<: *defaults
+  name: web
+worker:
+  <<: *defaults
+  name: worker
+  replicas: 5
+"#;
+
+    const STREAM: &'static str = r#"---
+name: web
+image: app:1
+replicas: 2
+---
+name: worker
+image: app:1
+replicas: 5
+"#;
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct ServicesConfiguration {
+        web: ServiceConfiguration,
+        worker: ServiceConfiguration,
+    }
+
+    #[test]
+    fn decode_resolves_anchors_and_merge_keys() {
+        // Act
+        let configuration: ServicesConfiguration = YamlCodec::new().decode(SERVICES.as_bytes()).unwrap();
+
+        // Assert
+        assert_eq!(configuration.web,
+                   ServiceConfiguration {
+                       name: "web".to_owned(),
+                       image: "app:latest".to_owned(),
+                       replicas: 2,
+                   });
+        assert_eq!(configuration.worker.replicas, 5);
+    }
+
+    #[test]
+    fn decode_invalid_yaml_returns_position() {
+        // Arrange
+        let yaml = b"name: web\nimage: [app\n";
+
+        // Act
+        let error = ConfigurationCodec::<ServiceConfiguration>::decode(&YamlCodec::new(), yaml).unwrap_err();
+
+        // Assert
+        assert!(error.line() > 0);
+    }
+
+    #[test]
+    fn decode_selects_document_by_index() {
+        // Arrange
+        let codec = YamlCodec::selecting(YamlDocumentSelector::Index(1));
+
+        // Act
+        let configuration: ServiceConfiguration = codec.decode(STREAM.as_bytes()).unwrap();
+
+        // Assert
+        assert_eq!(configuration.name, "worker");
+    }
+
+    #[test]
+    fn decode_selects_document_by_field() {
+        // Arrange
+        let codec = YamlCodec::selecting(YamlDocumentSelector::field("name", "web"));
+
+        // Act
+        let configuration: ServiceConfiguration = codec.decode(STREAM.as_bytes()).unwrap();
+
+        // Assert
+        assert_eq!(configuration.replicas, 2);
+    }
+
+    #[test]
+    fn decode_unmatched_selector_returns_no_document() {
+        // Arrange
+        let codec = YamlCodec::selecting(YamlDocumentSelector::field("name", "scheduler"));
+
+        // Act
+        let error = ConfigurationCodec::<ServiceConfiguration>::decode(&codec, STREAM.as_bytes()).unwrap_err();
+
+        // Assert
+        match error {
+            YamlCodecError::NoDocument => {}
+            other => panic!("expected no document, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn encode_update_replaces_only_selected_document() {
+        // Arrange
+        let codec = YamlCodec::selecting(YamlDocumentSelector::field("name", "worker"));
+        let mut configuration: ServiceConfiguration = codec.decode(STREAM.as_bytes()).unwrap();
+        configuration.replicas = 10;
+
+        // Act
+        let encoded = codec.encode_update(&configuration, STREAM.as_bytes()).unwrap();
+
+        // Assert
+        let web: ServiceConfiguration = YamlCodec::selecting(YamlDocumentSelector::Index(0)).decode(&encoded).unwrap();
+        let worker: ServiceConfiguration = codec.decode(&encoded).unwrap();
+        assert_eq!(web.replicas, 2);
+        assert_eq!(worker.replicas, 10);
+    }
+}
@@ -0,0 +1,59 @@
+use serde_yaml::Error as SerdeYamlError;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The error when a configuration could not be decoded from or encoded to YAML.
+#[derive(Debug)]
+pub enum YamlCodecError {
+    Yaml(SerdeYamlError),
+    NoDocument,
+}
+
+impl YamlCodecError {
+    /// Gets the one-based line at which decoding failed, this is 0 if the position is not known.
+    pub fn line(&self) -> usize {
+        match *self {
+            YamlCodecError::Yaml(ref error) => error.location().map_or(0, |location| location.line()),
+            YamlCodecError::NoDocument => 0,
+        }
+    }
+
+    /// Gets the one-based column at which decoding failed, this is 0 if the position is not known.
+    pub fn column(&self) -> usize {
+        match *self {
+            YamlCodecError::Yaml(ref error) => error.location().map_or(0, |location| location.column()),
+            YamlCodecError::NoDocument => 0,
+        }
+    }
+}
+
+impl From<SerdeYamlError> for YamlCodecError {
+    fn from(error: SerdeYamlError) -> Self {
+        YamlCodecError::Yaml(error)
+    }
+}
+
+impl Display for YamlCodecError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            YamlCodecError::Yaml(ref error) => write!(f, "{}", error),
+            YamlCodecError::NoDocument => write!(f, "no document matched the selector"),
+        }
+    }
+}
+
+impl Error for YamlCodecError {
+    fn description(&self) -> &str {
+        match *self {
+            YamlCodecError::Yaml(_) => "invalid yaml configuration",
+            YamlCodecError::NoDocument => "no document matched the selector",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            YamlCodecError::Yaml(ref error) => Some(error),
+            YamlCodecError::NoDocument => None,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use serde_yaml::Value;
+
+/// Selects a single document from a stream of YAML documents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum YamlDocumentSelector {
+    /// Selects the document at the zero-based index in the stream.
+    Index(usize),
+    /// Selects the first document with the value at the path of nested keys.
+    Field(Vec<String>, String),
+}
+
+impl YamlDocumentSelector {
+    /// Creates a selector for the first document where the field at the dot separated path has the specified value.
+    pub fn field<V: Into<String>>(path: &str, value: V) -> Self {
+        YamlDocumentSelector::Field(path.split('.').map(str::to_owned).collect(), value.into())
+    }
+
+    /// Returns whether the document at the specified index of the stream is selected.
+    pub fn matches(&self, index: usize, document: &Value) -> bool {
+        match *self {
+            YamlDocumentSelector::Index(selected) => index == selected,
+            YamlDocumentSelector::Field(ref path, ref expected) => {
+                let field = path.iter().fold(Some(document), |value, key| value.and_then(|value| value.get(key)));
+
+                match field {
+                    Some(&Value::String(ref value)) => value == expected,
+                    Some(&Value::Number(ref value)) => value.to_string() == *expected,
+                    Some(&Value::Bool(value)) => value.to_string() == *expected,
+                    _ => false,
+                }
+            }
+        }
+    }
+}
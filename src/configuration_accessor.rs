@@ -1,4 +1,4 @@
-use super::{ConfigurationReader, ConfigurationWriter};
+use super::{BoxConfigurationReader, BoxConfigurationWriter, ConfigurationReader, ConfigurationWriter};
 
 /// A struct which encapsulates both a `ConfigurationReader` and a `ConfigurationWriter`.
 #[derive(Debug, Default, Clone)]
@@ -20,6 +20,26 @@ impl<R, W> ConfigurationAccessor<R, W>
     }
 }
 
+/// A `ConfigurationAccessor` which hides the types of its reader and writer.
+pub type BoxConfigurationAccessor<C, E> = ConfigurationAccessor<BoxConfigurationReader<C, E>, BoxConfigurationWriter<C, E>>;
+
+impl<C, E> ConfigurationAccessor<BoxConfigurationReader<C, E>, BoxConfigurationWriter<C, E>>
+    where C: Send + 'static,
+          E: Send + 'static
+{
+    /// Creates a new `BoxConfigurationAccessor<C, E>` which reads from and writes to clones of the accessor,
+    /// converting its errors with `Into`.
+    pub fn boxed<A>(accessor: A) -> Self
+        where A: ConfigurationReader<Configuration = C> + ConfigurationWriter<Configuration = C> + Clone + Send + Sync + 'static,
+              <A as ConfigurationReader>::Error: Into<E>,
+              <A as ConfigurationWriter>::Error: Into<E>
+    {
+        Self {
+            reader: BoxConfigurationReader::new(accessor.clone()),
+            writer: BoxConfigurationWriter::new(accessor),
+        }
+    }
+}
 
 impl<R, W> Into<(R, W)> for ConfigurationAccessor<R, W> {
     fn into(self) -> (R, W) {
@@ -0,0 +1,22 @@
+use std::fmt::Debug;
+
+/// The trait for types which convert a configuration to and from bytes.
+pub trait ConfigurationCodec<C> {
+    /// The type of the error, this may be `!` if no error could occur.
+    type Error: Debug + Send + 'static;
+
+    /// Decodes a configuration from the specified bytes.
+    fn decode(&self, bytes: &[u8]) -> Result<C, Self::Error>;
+
+    /// Encodes the specified configuration into bytes.
+    fn encode(&self, configuration: &C) -> Result<Vec<u8>, Self::Error>;
+
+    /// Encodes the specified configuration into bytes which replace `existing`, the previously encoded bytes.
+    ///
+    /// Codecs for formats which are edited by hand can use this to preserve the comments and layout of
+    /// `existing`, by default this is the same as `encode`.
+    fn encode_update(&self, configuration: &C, existing: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        let _ = existing;
+        self.encode(configuration)
+    }
+}
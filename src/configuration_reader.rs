@@ -1,5 +1,6 @@
 use futures::Future;
 use std::fmt::Debug;
+use std::sync::Arc;
 
 /// The trait for types which read a configuration.
 pub trait ConfigurationReader {
@@ -17,3 +18,33 @@ pub trait ConfigurationReader {
     /// Asynchronously reads the configuration returning an error in cases of failure.
     fn read_configuration(&self) -> Self::ReadResult;
 }
+
+impl<'a, R: ConfigurationReader + ?Sized> ConfigurationReader for &'a R {
+    type Configuration = R::Configuration;
+    type Error = R::Error;
+    type ReadResult = R::ReadResult;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        (**self).read_configuration()
+    }
+}
+
+impl<R: ConfigurationReader + ?Sized> ConfigurationReader for Box<R> {
+    type Configuration = R::Configuration;
+    type Error = R::Error;
+    type ReadResult = R::ReadResult;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        (**self).read_configuration()
+    }
+}
+
+impl<R: ConfigurationReader + ?Sized> ConfigurationReader for Arc<R> {
+    type Configuration = R::Configuration;
+    type Error = R::Error;
+    type ReadResult = R::ReadResult;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        (**self).read_configuration()
+    }
+}
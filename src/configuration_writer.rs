@@ -16,4 +16,24 @@ pub trait ConfigurationWriter {
 
     /// Asynchronously writes the configuration returning an error in cases of failure.
     fn write_configuration(&mut self, configuration: &Self::Configuration) -> Self::WriteResult;
-}
\ No newline at end of file
+}
+
+impl<'a, W: ConfigurationWriter + ?Sized> ConfigurationWriter for &'a mut W {
+    type Configuration = W::Configuration;
+    type Error = W::Error;
+    type WriteResult = W::WriteResult;
+
+    fn write_configuration(&mut self, configuration: &Self::Configuration) -> Self::WriteResult {
+        (**self).write_configuration(configuration)
+    }
+}
+
+impl<W: ConfigurationWriter + ?Sized> ConfigurationWriter for Box<W> {
+    type Configuration = W::Configuration;
+    type Error = W::Error;
+    type WriteResult = W::WriteResult;
+
+    fn write_configuration(&mut self, configuration: &Self::Configuration) -> Self::WriteResult {
+        (**self).write_configuration(configuration)
+    }
+}
@@ -0,0 +1,54 @@
+use {ConfigurationReader, ConfigurationWriter};
+use super::CopyOnReadConfigurationReader;
+use super::copy_on_read_state::CopyOnReadState;
+use futures::{BoxFuture, Future};
+use futures::future;
+use std::sync::Arc;
+
+/// A `ConfigurationReader` which reads the cache of a `CopyOnReadConfigurationReader` until it is cleared
+/// through a `CopyOnReadHandle`.
+///
+/// Once cleared the read fails with `None` until the configuration is copied to the cache again, any other
+/// error is `Some` error of the cache.
+#[derive(Debug)]
+pub struct ClearableConfigurationReader<R> {
+    reader: R,
+    state: Arc<CopyOnReadState>,
+}
+
+impl<R: ConfigurationReader> ClearableConfigurationReader<R> {
+    /// Creates a new `ClearableConfigurationReader` of the cache the copy writes to.
+    pub(crate) fn new<CR, W>(reader: R, copy: &CopyOnReadConfigurationReader<CR, W>) -> Self
+        where CR: ConfigurationReader,
+              W: ConfigurationWriter
+    {
+        Self {
+            reader: reader,
+            state: copy.state().clone(),
+        }
+    }
+}
+
+impl<R> ClearableConfigurationReader<R> {
+    /// Gets the reader of the cache.
+    pub fn reader(&self) -> &R {
+        &self.reader
+    }
+}
+
+impl<R: ConfigurationReader> ConfigurationReader for ClearableConfigurationReader<R> {
+    type Configuration = R::Configuration;
+    type Error = Option<R::Error>;
+    type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        if self.state.is_cleared() {
+            return future::err(None).boxed();
+        }
+
+        self.reader
+            .read_configuration()
+            .map_err(Some)
+            .boxed()
+    }
+}
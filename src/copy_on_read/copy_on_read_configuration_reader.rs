@@ -1,11 +1,20 @@
 use {ConfigurationReader, ConfigurationWriter, CopyConfigurationError};
-use sync::ConcurrentWriter;
+use super::copy_on_read_state::CopyOnReadState;
+use sync::{ConcurrentWriter, SingleFlight};
 use futures::{Future, BoxFuture};
+use futures::future::{self, SharedError};
+use std::sync::Arc;
+use either::Either;
 
-#[derive(Debug, Default, Clone)]
-pub struct CopyOnReadConfigurationReader<R, W> {
-    reader: R,
+/// A `ConfigurationReader` which writes every configuration it reads to a `ConfigurationWriter`.
+///
+/// Concurrent reads share one read and write, each receiving its configuration or error.
+#[derive(Debug)]
+pub struct CopyOnReadConfigurationReader<R: ConfigurationReader, W: ConfigurationWriter> {
+    reader: Arc<R>,
     writer: ConcurrentWriter<W>,
+    copy: SingleFlight<R::Configuration, CopyConfigurationError<R::Error, W::Error>>,
+    state: Arc<CopyOnReadState>,
 }
 
 impl<R, W> CopyOnReadConfigurationReader<R, W>
@@ -14,21 +23,137 @@ impl<R, W> CopyOnReadConfigurationReader<R, W>
 {
     pub fn new(reader: R, writer: W) -> Self {
         Self {
-            reader: reader,
+            reader: Arc::new(reader),
             writer: ConcurrentWriter::new(writer),
+            copy: SingleFlight::new(),
+            state: Arc::new(CopyOnReadState::new()),
+        }
+    }
+}
+
+impl<R: ConfigurationReader, W: ConfigurationWriter> CopyOnReadConfigurationReader<R, W> {
+    pub fn reader(&self) -> &R {
+        &self.reader
+    }
+
+    pub(super) fn state(&self) -> &Arc<CopyOnReadState> {
+        &self.state
+    }
+}
+
+impl<R, W> CopyOnReadConfigurationReader<R, W>
+    where R: ConfigurationReader,
+          W: ConfigurationWriter<Configuration = R::Configuration> + Send + 'static
+{
+    /// Reads the configuration and writes it, unless the cache has been cleared or refreshed since the
+    /// generation.
+    pub(super) fn copy(&self, generation: usize) -> BoxFuture<R::Configuration, CopyConfigurationError<R::Error, W::Error>> {
+        let mut writer = self.writer.clone();
+        let state = self.state.clone();
+
+        self.reader
+            .read_configuration()
+            .map_err(CopyConfigurationError::ReadError)
+            .and_then(move |configuration| {
+                let write = state.write_if_generation(generation, || writer.write_configuration(&configuration));
+
+                let copy = match write {
+                    Some(write) => {
+                        Either::Left(write.map_err(CopyConfigurationError::WriteError)
+                            .map(move |()| {
+                                state.copied(generation);
+                                configuration
+                            }))
+                    }
+                    None => Either::Right(future::ok(configuration)),
+                };
+
+                copy.either(Future::boxed, Future::boxed)
+            })
+            .boxed()
+    }
+}
+
+impl<R: ConfigurationReader, W: ConfigurationWriter> Clone for CopyOnReadConfigurationReader<R, W> {
+    fn clone(&self) -> Self {
+        // Clone should share the same reader, writer, copy in flight and state
+        Self {
+            reader: self.reader.clone(),
+            writer: self.writer.clone(),
+            copy: self.copy.clone(),
+            state: self.state.clone(),
         }
     }
 }
 
 impl<R, W> ConfigurationReader for CopyOnReadConfigurationReader<R, W>
     where R: ConfigurationReader + 'static,
-          W: ConfigurationWriter<Configuration = R::Configuration> + Send + 'static
+          R::Configuration: Clone + Sync,
+          R::Error: Sync,
+          W: ConfigurationWriter<Configuration = R::Configuration> + Send + 'static,
+          W::Error: Sync
 {
     type Configuration = R::Configuration;
-    type Error = CopyConfigurationError<R::Error, W::Error>;
+    type Error = SharedError<CopyConfigurationError<R::Error, W::Error>>;
     type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
 
     fn read_configuration(&self) -> Self::ReadResult {
-        ::copy_configuration(&self.reader, self.writer.clone()).boxed()
+        self.copy.run(|| self.copy(self.state.generation()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationReader, ConfigurationWriter, FluentConfigurationReader};
+    use closure::ClosureConfigurationReader;
+    use memory::MemoryConfigurationAccessor;
+    use futures::Future;
+    use futures::future::join_all;
+    use futures::sync::oneshot;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn with_cache_concurrent_misses_share_one_read() {
+        // Arrange
+        let reads = Arc::new(AtomicUsize::new(0));
+        let (complete, completed) = oneshot::channel::<u32>();
+        let completed = completed.shared();
+
+        let reader_reads = reads.clone();
+        let (reader, _) = ClosureConfigurationReader::new(move || {
+                reader_reads.fetch_add(1, Ordering::SeqCst);
+                completed.clone().map(|configuration| *configuration).map_err(|_| "cancelled")
+            })
+            .with_cache(MemoryConfigurationAccessor::empty());
+
+        // Act
+        // The cache misses once the reads are polled, so complete the read after they are all waiting
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            complete.send(4).unwrap();
+        });
+        let configurations = join_all((0..3).map(|_| reader.read_configuration())).wait().unwrap();
+
+        // Assert
+        assert_eq!(reads.load(Ordering::SeqCst), 1);
+        assert_eq!(configurations, vec![4, 4, 4]);
+    }
+
+    #[test]
+    fn with_cache_cleared_reads_again() {
+        // Arrange
+        let mut origin = MemoryConfigurationAccessor::new(1);
+        let (reader, handle) = origin.clone().with_cache(MemoryConfigurationAccessor::empty());
+        reader.read_configuration().wait().unwrap();
+        origin.write_configuration(&2).wait().unwrap();
+
+        // Act
+        handle.clear();
+
+        // Assert
+        assert_eq!(reader.read_configuration().wait().unwrap(), 2);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,123 @@
+use {ConfigurationReader, ConfigurationWriter, CopyConfigurationError};
+use super::CopyOnReadConfigurationReader;
+use futures::BoxFuture;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::time::Instant;
+
+/// A handle to the configuration cached by `FluentConfigurationReader::with_cache`, returned with the reader.
+///
+/// The handle shares the cache of the reader it was returned with, so clearing or refreshing through the
+/// handle is seen by every clone of the reader.
+pub struct CopyOnReadHandle<R: ConfigurationReader, W: ConfigurationWriter> {
+    copy: CopyOnReadConfigurationReader<R, W>,
+}
+
+impl<R: ConfigurationReader, W: ConfigurationWriter> CopyOnReadHandle<R, W> {
+    pub(crate) fn new(copy: CopyOnReadConfigurationReader<R, W>) -> Self {
+        Self { copy: copy }
+    }
+
+    /// Clears the cached configuration, the next read reads the configuration again.
+    ///
+    /// The cache itself is not written, the configuration in it is ignored until a new one is copied to it.
+    pub fn clear(&self) {
+        self.copy.state().clear();
+    }
+
+    /// Gets whether a configuration read through the reader is cached.
+    pub fn is_cached(&self) -> bool {
+        self.copy.state().cached_at().is_some()
+    }
+
+    /// Gets when the cached configuration was cached.
+    pub fn cached_at(&self) -> Option<Instant> {
+        self.copy.state().cached_at()
+    }
+}
+
+impl<R, W> CopyOnReadHandle<R, W>
+    where R: ConfigurationReader,
+          W: ConfigurationWriter<Configuration = R::Configuration> + Send + 'static
+{
+    /// Reads the configuration again, caching it when the read succeeds.
+    ///
+    /// A read already in flight is not shared and is not cached, so the configuration is read after `refresh`
+    /// is called. The cached configuration is kept when the read fails.
+    pub fn refresh(&self) -> BoxFuture<R::Configuration, CopyConfigurationError<R::Error, W::Error>> {
+        let generation = self.copy.state().supersede();
+        self.copy.copy(generation)
+    }
+}
+
+impl<R: ConfigurationReader, W: ConfigurationWriter> Clone for CopyOnReadHandle<R, W> {
+    fn clone(&self) -> Self {
+        Self { copy: self.copy.clone() }
+    }
+}
+
+impl<R: ConfigurationReader, W: ConfigurationWriter> Debug for CopyOnReadHandle<R, W> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("CopyOnReadHandle")
+            .field("cached_at", &self.cached_at())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationReader, ConfigurationWriter, FluentConfigurationReader};
+    use closure::ClosureConfigurationReader;
+    use memory::MemoryConfigurationAccessor;
+    use futures::Future;
+    use futures::future::ok;
+    use futures::sync::oneshot;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn refresh_caches_new_configuration() {
+        // Arrange
+        let mut origin = MemoryConfigurationAccessor::new(1);
+        let (reader, handle) = origin.clone().with_cache(MemoryConfigurationAccessor::empty());
+        reader.read_configuration().wait().unwrap();
+        origin.write_configuration(&2).wait().unwrap();
+
+        // Act
+        let refreshed = handle.refresh().wait().unwrap();
+
+        // Assert
+        assert_eq!(refreshed, 2);
+        assert_eq!(reader.read_configuration().wait().unwrap(), 2);
+        assert!(handle.is_cached());
+    }
+
+    #[test]
+    fn refresh_does_not_share_read_in_flight() {
+        // Arrange
+        let reads = Arc::new(AtomicUsize::new(0));
+        let (complete, completed) = oneshot::channel::<u32>();
+        let completed = completed.shared();
+        let cache = MemoryConfigurationAccessor::empty();
+
+        let reader_reads = reads.clone();
+        let (reader, handle) = ClosureConfigurationReader::new(move || {
+                let configuration: Box<Future<Item = u32, Error = &'static str> + Send> = match reader_reads.fetch_add(1, Ordering::SeqCst) {
+                    0 => Box::new(completed.clone().map(|configuration| *configuration).map_err(|_| "cancelled")),
+                    _ => Box::new(ok(2)),
+                };
+                configuration
+            })
+            .with_cache(cache.clone());
+        let in_flight = reader.fallback().read_configuration();
+
+        // Act
+        let refreshed = handle.refresh().wait().unwrap();
+        complete.send(1).unwrap();
+        in_flight.wait().unwrap();
+
+        // Assert
+        assert_eq!(refreshed, 2);
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+        assert_eq!(cache.read_configuration().wait().unwrap(), 2);
+    }
+}
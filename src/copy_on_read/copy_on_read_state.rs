@@ -0,0 +1,69 @@
+use std::sync::Mutex;
+use std::time::Instant;
+
+#[derive(Debug, Default)]
+struct CopyOnReadEntry {
+    // Changes whenever the cache is cleared or a refresh starts, so a copy started before is not cached over it
+    generation: usize,
+    cleared: bool,
+    cached_at: Option<Instant>,
+}
+
+/// The state shared by a `CopyOnReadConfigurationReader`, its clones and its `CopyOnReadHandle`s.
+#[derive(Debug, Default)]
+pub(super) struct CopyOnReadState {
+    entry: Mutex<CopyOnReadEntry>,
+}
+
+impl CopyOnReadState {
+    pub(super) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(super) fn clear(&self) {
+        let mut entry = self.entry.lock().unwrap();
+        entry.generation += 1;
+        entry.cleared = true;
+        entry.cached_at = None;
+    }
+
+    pub(super) fn is_cleared(&self) -> bool {
+        self.entry.lock().unwrap().cleared
+    }
+
+    pub(super) fn cached_at(&self) -> Option<Instant> {
+        self.entry.lock().unwrap().cached_at
+    }
+
+    pub(super) fn generation(&self) -> usize {
+        self.entry.lock().unwrap().generation
+    }
+
+    /// Starts a new generation, so the copies in flight are not cached.
+    pub(super) fn supersede(&self) -> usize {
+        let mut entry = self.entry.lock().unwrap();
+        entry.generation += 1;
+        entry.generation
+    }
+
+    /// Calls `write` unless the cache has been cleared or refreshed since the generation.
+    pub(super) fn write_if_generation<F, T>(&self, generation: usize, write: F) -> Option<T>
+        where F: FnOnce() -> T
+    {
+        let entry = self.entry.lock().unwrap();
+        if entry.generation == generation {
+            Some(write())
+        } else {
+            None
+        }
+    }
+
+    /// Records that the copy of the generation was cached, unless the cache has been cleared or refreshed since.
+    pub(super) fn copied(&self, generation: usize) {
+        let mut entry = self.entry.lock().unwrap();
+        if entry.generation == generation {
+            entry.cleared = false;
+            entry.cached_at = Some(Instant::now());
+        }
+    }
+}
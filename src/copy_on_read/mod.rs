@@ -1,2 +1,10 @@
+mod copy_on_read_state;
+
 mod copy_on_read_configuration_reader;
-pub use self::copy_on_read_configuration_reader::*;
\ No newline at end of file
+pub use self::copy_on_read_configuration_reader::*;
+
+mod clearable_configuration_reader;
+pub use self::clearable_configuration_reader::*;
+
+mod copy_on_read_handle;
+pub use self::copy_on_read_handle::*;
\ No newline at end of file
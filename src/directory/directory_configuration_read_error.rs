@@ -0,0 +1,56 @@
+use file::FileConfigurationReadError;
+use value::ConfigurationValueError;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::Error as IoError;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum DirectoryConfigurationReadError<E> {
+    /// The directory could not be listed.
+    Io(IoError),
+    /// The fragment at the path could not be read or decoded.
+    Fragment(PathBuf, FileConfigurationReadError<E>),
+    /// The merged fragments do not form a valid configuration.
+    Invalid(ConfigurationValueError),
+}
+
+impl<E> DirectoryConfigurationReadError<E> {
+    /// Gets the path of the fragment which caused this error, if any.
+    pub fn fragment(&self) -> Option<&Path> {
+        match *self {
+            DirectoryConfigurationReadError::Fragment(ref path, _) => Some(path),
+            _ => None,
+        }
+    }
+}
+
+impl<E: Display> Display for DirectoryConfigurationReadError<E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            DirectoryConfigurationReadError::Io(ref err) => write!(f, "Io Error {}", err),
+            DirectoryConfigurationReadError::Fragment(ref path, ref err) => {
+                write!(f, "Fragment {} {}", path.display(), err)
+            }
+            DirectoryConfigurationReadError::Invalid(ref err) => write!(f, "Invalid Configuration {}", err),
+        }
+    }
+}
+
+impl<E: Error> Error for DirectoryConfigurationReadError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            DirectoryConfigurationReadError::Io(ref err) => err.description(),
+            DirectoryConfigurationReadError::Fragment(_, ref err) => err.description(),
+            DirectoryConfigurationReadError::Invalid(ref err) => err.description(),
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            DirectoryConfigurationReadError::Io(ref err) => Some(err),
+            DirectoryConfigurationReadError::Fragment(_, ref err) => Some(err),
+            DirectoryConfigurationReadError::Invalid(ref err) => Some(err),
+        }
+    }
+}
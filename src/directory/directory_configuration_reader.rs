@@ -0,0 +1,201 @@
+use {ConfigurationCodec, ConfigurationReader};
+use super::DirectoryConfigurationReadError;
+use super::file_name_pattern;
+use file::FileConfigurationReadError;
+use value::ConfigurationValue;
+use futures::IntoFuture;
+use futures::future::FutureResult;
+use serde::de::DeserializeOwned;
+use std::fs::{self, File};
+use std::io::{Error as IoError, Read};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+/// A `ConfigurationReader` which reads every file in a directory whose name matches a pattern, such as
+/// the fragments in `/etc/app/conf.d/*.toml`, and merges them into one configuration.
+///
+/// Fragments are decoded into a `ConfigurationValue` and merged in the lexical order of their file names,
+/// so a later fragment overrides the keys it shares with an earlier fragment while keeping the rest.
+/// The pattern may contain `*` and `?` wildcards, hidden files are only read when the pattern starts with `.`.
+#[derive(Debug)]
+pub struct DirectoryConfigurationReader<C, Codec> {
+    directory: PathBuf,
+    pattern: String,
+    codec: Codec,
+    phantom_data: PhantomData<C>,
+}
+
+impl<C, Codec> DirectoryConfigurationReader<C, Codec>
+    where Codec: ConfigurationCodec<ConfigurationValue>
+{
+    /// Creates a new `DirectoryConfigurationReader<C, Codec>` for every file in the specified directory.
+    pub fn new<P: Into<PathBuf>>(directory: P, codec: Codec) -> Self {
+        Self {
+            directory: directory.into(),
+            pattern: "*".to_owned(),
+            codec: codec,
+            phantom_data: Default::default(),
+        }
+    }
+
+    /// Only reads the files whose names match the pattern, for example `*.toml`.
+    pub fn with_pattern<S: Into<String>>(mut self, pattern: S) -> Self {
+        self.pattern = pattern.into();
+        self
+    }
+}
+
+impl<C, Codec> DirectoryConfigurationReader<C, Codec> {
+    /// Gets the path of the directory containing the fragments.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Gets the pattern which the names of fragments must match.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Gets the codec used to decode each fragment.
+    pub fn codec(&self) -> &Codec {
+        &self.codec
+    }
+
+    /// Gets the paths of the fragments in the order they are merged.
+    pub fn fragments(&self) -> Result<Vec<PathBuf>, IoError> {
+        let mut fragments = Vec::new();
+        for entry in fs::read_dir(&self.directory)? {
+            let entry = entry?;
+
+            let is_match = entry.file_name()
+                .to_str()
+                .map_or(false, |name| file_name_pattern::matches(&self.pattern, name));
+
+            if is_match && fs::metadata(entry.path())?.is_file() {
+                fragments.push(entry.path());
+            }
+        }
+
+        fragments.sort();
+        Ok(fragments)
+    }
+}
+
+impl<C, Codec> DirectoryConfigurationReader<C, Codec>
+    where Codec: ConfigurationCodec<ConfigurationValue>
+{
+    fn read_fragment(&self, path: &Path) -> Result<ConfigurationValue, FileConfigurationReadError<Codec::Error>> {
+        let mut bytes = Vec::new();
+        File::open(path)
+            .and_then(|mut file| file.read_to_end(&mut bytes))
+            .map_err(FileConfigurationReadError::Io)?;
+
+        self.codec.decode(&bytes).map_err(FileConfigurationReadError::Decode)
+    }
+
+    fn read_merged(&self) -> Result<ConfigurationValue, DirectoryConfigurationReadError<Codec::Error>> {
+        let mut merged = ConfigurationValue::table();
+        for path in self.fragments().map_err(DirectoryConfigurationReadError::Io)? {
+            match self.read_fragment(&path) {
+                Ok(fragment) => merged.merge(fragment),
+                Err(err) => return Err(DirectoryConfigurationReadError::Fragment(path, err)),
+            }
+        }
+
+        Ok(merged)
+    }
+}
+
+impl<C, Codec: Clone> Clone for DirectoryConfigurationReader<C, Codec> {
+    fn clone(&self) -> Self {
+        Self {
+            directory: self.directory.clone(),
+            pattern: self.pattern.clone(),
+            codec: self.codec.clone(),
+            phantom_data: Default::default(),
+        }
+    }
+}
+
+impl<C, Codec> ConfigurationReader for DirectoryConfigurationReader<C, Codec>
+    where C: DeserializeOwned + Send + 'static,
+          Codec: ConfigurationCodec<ConfigurationValue>
+{
+    type Configuration = C;
+    type Error = DirectoryConfigurationReadError<Codec::Error>;
+    type ReadResult = FutureResult<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        self.read_merged()
+            .and_then(|merged| merged.deserialize_into().map_err(DirectoryConfigurationReadError::Invalid))
+            .into_future()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ConfigurationReader;
+    use codec::IniCodec;
+    use directory::DirectoryConfigurationReader;
+    use futures::Future;
+    use std::fs::File;
+    use std::io::Write;
+    use tempdir::TempDir;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct ServerConfiguration {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct TestConfiguration {
+        name: String,
+        server: ServerConfiguration,
+    }
+
+    fn fragment(directory: &TempDir, name: &str, contents: &str) {
+        File::create(directory.path().join(name)).unwrap().write_all(contents.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn read_configuration_merges_fragments_in_order() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        fragment(&directory, "20-site.ini", "[server]\nport = 9090\n");
+        fragment(&directory, "10-defaults.ini", "name = app\n[server]\nhost = localhost\nport = 8080\n");
+        fragment(&directory, "30-ignored.ini.bak", "name = ignored\n");
+
+        let reader = DirectoryConfigurationReader::<TestConfiguration, _>::new(directory.path(), IniCodec::new())
+            .with_pattern("*.ini");
+
+        // Act
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration,
+                   TestConfiguration {
+                       name: "app".to_owned(),
+                       server: ServerConfiguration {
+                           host: "localhost".to_owned(),
+                           port: 9090,
+                       },
+                   });
+    }
+
+    #[test]
+    fn read_configuration_invalid_fragment_returns_fragment_path() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        fragment(&directory, "10-defaults.ini", "name = app\n");
+        fragment(&directory, "20-broken.ini", "[server\n");
+
+        let reader = DirectoryConfigurationReader::<TestConfiguration, _>::new(directory.path(), IniCodec::new());
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        assert_eq!(error.fragment(), Some(directory.path().join("20-broken.ini").as_path()));
+    }
+}
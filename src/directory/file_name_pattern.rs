@@ -0,0 +1,63 @@
+/// Returns whether a file name matches a pattern, where `*` matches any run of characters and `?` matches
+/// any single character.
+///
+/// Hidden file names which start with `.` only match patterns which also start with `.`.
+pub(super) fn matches(pattern: &str, name: &str) -> bool {
+    if name.starts_with('.') && !pattern.starts_with('.') {
+        return false;
+    }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // The position after the last `*` and the position in the name it was tried against
+    let mut backtrack: Option<(usize, usize)> = None;
+    let (mut p, mut n) = (0, 0);
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some(&'*') => {
+                backtrack = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => {
+                match backtrack {
+                    Some((star, tried)) => {
+                        p = star;
+                        n = tried + 1;
+                        backtrack = Some((star, tried + 1));
+                    }
+                    None => return false,
+                }
+            }
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::matches;
+
+    #[test]
+    fn matches_wildcards() {
+        // Assert
+        assert!(matches("*.toml", "10-defaults.toml"));
+        assert!(matches("??-*.toml", "10-defaults.toml"));
+        assert!(matches("*", "app.conf"));
+        assert!(!matches("*.toml", "10-defaults.toml.bak"));
+        assert!(!matches("?.toml", "10.toml"));
+    }
+
+    #[test]
+    fn matches_hidden_files_only_explicitly() {
+        // Assert
+        assert!(!matches("*.toml", ".10-defaults.toml"));
+        assert!(matches(".*.toml", ".10-defaults.toml"));
+    }
+}
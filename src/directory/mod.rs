@@ -0,0 +1,7 @@
+mod file_name_pattern;
+
+mod directory_configuration_read_error;
+pub use self::directory_configuration_read_error::*;
+
+mod directory_configuration_reader;
+pub use self::directory_configuration_reader::*;
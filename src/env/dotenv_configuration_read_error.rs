@@ -0,0 +1,34 @@
+use super::EnvConfigurationReadError;
+use std::io::{Error as IoError, ErrorKind};
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum DotenvConfigurationReadError {
+        Io(err: IoError) {
+            description(err.description())
+            display("unable to read the dotenv file: {}", err)
+            cause(err)
+            from()
+        }
+        Syntax(line: usize, message: String) {
+            description("invalid dotenv file")
+            display("invalid dotenv file at line {}: {}", line, message)
+        }
+        Env(err: EnvConfigurationReadError) {
+            description(err.description())
+            display("{}", err)
+            cause(err)
+            from()
+        }
+    }
+}
+
+impl DotenvConfigurationReadError {
+    /// Returns whether this error was caused by the dotenv file not existing.
+    pub fn is_not_found(&self) -> bool {
+        match *self {
+            DotenvConfigurationReadError::Io(ref err) => err.kind() == ErrorKind::NotFound,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use ConfigurationReader;
+use super::{DotenvConfigurationReadError, EnvKeyMapping};
+use super::dotenv_parser;
+use value::ConfigurationValue;
+use futures::IntoFuture;
+use futures::future::FutureResult;
+use serde::de::DeserializeOwned;
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+/// A `ConfigurationReader` which reads a configuration from the variables defined in a `.env` file.
+///
+/// Values may be quoted, span several lines within double quotes and reference other variables with
+/// `${NAME}`, `${NAME:-default}` or `$NAME`. References are resolved from the process environment first and
+/// then from the variables defined earlier in the file.
+///
+/// The variables are mapped to nested keys by an `EnvKeyMapping` in the same way as an
+/// `EnvConfigurationReader`, so a `DotenvConfigurationReader` can be used as a fallback for the process
+/// environment.
+#[derive(Debug)]
+pub struct DotenvConfigurationReader<C = ConfigurationValue> {
+    path: PathBuf,
+    mapping: EnvKeyMapping,
+    overlay_environment: bool,
+    phantom_data: PhantomData<C>,
+}
+
+impl<C> DotenvConfigurationReader<C>
+    where C: DeserializeOwned
+{
+    /// Creates a new `DotenvConfigurationReader<C>` for the variables in the file at the specified path which start with the prefix.
+    pub fn new<P: Into<PathBuf>, S: Into<String>>(path: P, prefix: S) -> Self {
+        Self::with_mapping(path, EnvKeyMapping::new(prefix))
+    }
+
+    /// Creates a new `DotenvConfigurationReader<C>` for the file at the specified path using the specified `EnvKeyMapping`.
+    pub fn with_mapping<P: Into<PathBuf>>(path: P, mapping: EnvKeyMapping) -> Self {
+        Self {
+            path: path.into(),
+            mapping: mapping,
+            overlay_environment: false,
+            phantom_data: Default::default(),
+        }
+    }
+
+    /// Uses the specified separator between nested keys.
+    pub fn with_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.mapping = self.mapping.with_separator(separator);
+        self
+    }
+
+    /// Applies the mapped variables of the process environment over the variables of the file, so the file
+    /// only needs to define the variables which are missing from the environment.
+    pub fn with_environment_overlay(mut self) -> Self {
+        self.overlay_environment = true;
+        self
+    }
+}
+
+impl<C> DotenvConfigurationReader<C> {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn mapping(&self) -> &EnvKeyMapping {
+        &self.mapping
+    }
+
+    fn variables(&self) -> Result<Vec<(String, String)>, DotenvConfigurationReadError> {
+        let mut text = String::new();
+        File::open(&self.path)?.read_to_string(&mut text)?;
+
+        let mut variables = dotenv_parser::parse(&text, |name| env::var(name).ok())
+            .map_err(|(line, message)| DotenvConfigurationReadError::Syntax(line, message))?;
+
+        if self.overlay_environment {
+            // Later variables replace earlier ones which map to the same key
+            for (name, value) in env::vars_os() {
+                if let (Ok(name), Ok(value)) = (name.into_string(), value.into_string()) {
+                    if self.mapping.key_path(&name).is_some() {
+                        variables.push((name, value));
+                    }
+                }
+            }
+        }
+
+        Ok(variables)
+    }
+}
+
+impl<C> Clone for DotenvConfigurationReader<C> {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            mapping: self.mapping.clone(),
+            overlay_environment: self.overlay_environment,
+            phantom_data: Default::default(),
+        }
+    }
+}
+
+impl<C> ConfigurationReader for DotenvConfigurationReader<C>
+    where C: DeserializeOwned + Send + 'static
+{
+    type Configuration = C;
+    type Error = DotenvConfigurationReadError;
+    type ReadResult = FutureResult<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        self.variables()
+            .and_then(|variables| {
+                self.mapping
+                    .to_value(variables)
+                    .deserialize_into()
+                    .map_err(|err| DotenvConfigurationReadError::Env(self.mapping.read_error(err)))
+            })
+            .into_future()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ConfigurationReader;
+    use env::{DotenvConfigurationReader, EnvConfigurationReader};
+    use fallback::FallbackConfigurationReader;
+    use futures::Future;
+    use std::env;
+    use std::fs::File;
+    use std::io::Write;
+    use tempdir::TempDir;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct TestConfiguration {
+        url: String,
+        pool_size: u32,
+    }
+
+    fn dotenv_file(directory: &TempDir, contents: &str) -> ::std::path::PathBuf {
+        let path = directory.path().join(".env");
+        File::create(&path).unwrap().write_all(contents.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn read_configuration_maps_file_variables() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let path = dotenv_file(&directory, "export LZ_DOTENV_FILE_URL=\"postgres://localhost\"\nLZ_DOTENV_FILE_POOL_SIZE=4\n");
+
+        let reader = DotenvConfigurationReader::<TestConfiguration>::new(path, "LZ_DOTENV_FILE_");
+
+        // Act
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration,
+                   TestConfiguration {
+                       url: "postgres://localhost".to_owned(),
+                       pool_size: 4,
+                   });
+    }
+
+    #[test]
+    fn read_configuration_with_environment_overlay_prefers_environment() {
+        // Arrange
+        env::set_var("LZ_DOTENV_OVERLAY_POOL_SIZE", "16");
+
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let path = dotenv_file(&directory, "LZ_DOTENV_OVERLAY_URL=postgres://localhost\nLZ_DOTENV_OVERLAY_POOL_SIZE=4\n");
+
+        let reader = DotenvConfigurationReader::<TestConfiguration>::new(path, "LZ_DOTENV_OVERLAY_").with_environment_overlay();
+
+        // Act
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration.pool_size, 16);
+    }
+
+    #[test]
+    fn fallback_from_environment_uses_file() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let path = dotenv_file(&directory, "LZ_DOTENV_FALLBACK_URL=postgres://localhost\nLZ_DOTENV_FALLBACK_POOL_SIZE=4\n");
+
+        let reader = FallbackConfigurationReader::new(EnvConfigurationReader::<TestConfiguration>::new("LZ_DOTENV_FALLBACK_"),
+                                                      DotenvConfigurationReader::new(path, "LZ_DOTENV_FALLBACK_"));
+
+        // Act
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration.pool_size, 4);
+    }
+}
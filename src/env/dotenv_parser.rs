@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::iter::{Enumerate, Peekable};
+use std::str::{Chars, Lines};
+
+/// Parses the contents of a `.env` file into its variables in the order they are defined.
+///
+/// `lookup` is used to expand references to variables which are not defined earlier in the file.
+pub(super) fn parse<F>(text: &str, lookup: F) -> Result<Vec<(String, String)>, (usize, String)>
+    where F: Fn(&str) -> Option<String>
+{
+    let mut parser = Parser {
+        lines: text.lines().enumerate(),
+        defined: HashMap::new(),
+        lookup: lookup,
+    };
+
+    let mut variables = Vec::new();
+    while let Some((number, line)) = parser.next_line() {
+        if let Some((name, value)) = parser.parse_line(number, line)? {
+            parser.defined.insert(name.clone(), value.clone());
+            variables.push((name, value));
+        }
+    }
+
+    Ok(variables)
+}
+
+struct Parser<'a, F> {
+    lines: Enumerate<Lines<'a>>,
+    defined: HashMap<String, String>,
+    lookup: F,
+}
+
+impl<'a, F> Parser<'a, F>
+    where F: Fn(&str) -> Option<String>
+{
+    fn next_line(&mut self) -> Option<(usize, &'a str)> {
+        self.lines.next().map(|(index, line)| (index + 1, line))
+    }
+
+    fn parse_line(&mut self, number: usize, line: &'a str) -> Result<Option<(String, String)>, (usize, String)> {
+        let mut line = line.trim_start();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+
+        if line.starts_with("export") && line[6..].starts_with(char::is_whitespace) {
+            line = line[6..].trim_start();
+        }
+
+        let separator = match line.find('=') {
+            Some(separator) => separator,
+            None => return Err((number, "expected NAME=VALUE".to_owned())),
+        };
+
+        let name = line[..separator].trim();
+        let is_valid_name = !name.is_empty() &&
+                            name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+        if !is_valid_name {
+            return Err((number, format!("invalid variable name '{}'", name)));
+        }
+
+        let value = line[separator + 1..].trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ '"') |
+            Some(quote @ '\'') => self.parse_quoted(number, quote, &value[1..])?,
+            _ => {
+                // An unquoted value ends at a comment which is preceded by whitespace
+                let value = match value.find(" #").or_else(|| value.find("\t#")) {
+                    Some(comment) => &value[..comment],
+                    None => value,
+                };
+
+                self.expand(value.trim_end().chars().peekable(), false)
+            }
+        };
+
+        Ok(Some((name.to_owned(), value)))
+    }
+
+    fn parse_quoted(&mut self, number: usize, quote: char, first_line: &str) -> Result<String, (usize, String)> {
+        let mut quoted = String::new();
+        let mut line = first_line;
+
+        // A quoted value may continue over several lines until the closing quote
+        loop {
+            match closing_quote(line, quote) {
+                Some(end) => {
+                    quoted.push_str(&line[..end]);
+
+                    let remainder = line[end + 1..].trim();
+                    if !remainder.is_empty() && !remainder.starts_with('#') {
+                        return Err((number, "unexpected characters after quoted value".to_owned()));
+                    }
+
+                    break;
+                }
+                None => {
+                    quoted.push_str(line);
+                    quoted.push('\n');
+
+                    line = match self.next_line() {
+                        Some((_, line)) => line,
+                        None => return Err((number, "unterminated quoted value".to_owned())),
+                    };
+                }
+            }
+        }
+
+        if quote == '\'' {
+            Ok(quoted)
+        } else {
+            Ok(self.expand(quoted.chars().peekable(), true))
+        }
+    }
+
+    fn expand(&self, mut chars: Peekable<Chars>, escapes: bool) -> String {
+        let mut expanded = String::new();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if escapes => {
+                    match chars.next() {
+                        Some('n') => expanded.push('\n'),
+                        Some('r') => expanded.push('\r'),
+                        Some('t') => expanded.push('\t'),
+                        Some(other) => expanded.push(other),
+                        None => expanded.push('\\'),
+                    }
+                }
+                '$' if chars.peek() == Some(&'{') => {
+                    chars.next();
+
+                    let reference: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                    let (name, default) = match reference.find(":-") {
+                        Some(index) => (&reference[..index], Some(&reference[index + 2..])),
+                        None => (reference.as_str(), None),
+                    };
+
+                    match self.resolve(name) {
+                        Some(ref value) if !value.is_empty() => expanded.push_str(value),
+                        _ => expanded.push_str(default.unwrap_or("")),
+                    }
+                }
+                '$' if chars.peek().map_or(false, |&c| c.is_ascii_alphabetic() || c == '_') => {
+                    let mut name = String::new();
+                    while let Some(&c) = chars.peek() {
+                        if !c.is_ascii_alphanumeric() && c != '_' {
+                            break;
+                        }
+
+                        name.push(c);
+                        chars.next();
+                    }
+
+                    expanded.push_str(&self.resolve(&name).unwrap_or_default());
+                }
+                c => expanded.push(c),
+            }
+        }
+
+        expanded
+    }
+
+    fn resolve(&self, name: &str) -> Option<String> {
+        (self.lookup)(name).or_else(|| self.defined.get(name).cloned())
+    }
+}
+
+fn closing_quote(line: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '\\' if quote == '"' && !escaped => escaped = true,
+            c if c == quote && !escaped => return Some(index),
+            _ => escaped = false,
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    fn variables(text: &str) -> Vec<(String, String)> {
+        parse(text, |name| if name == "HOME" { Some("/home/app".to_owned()) } else { None }).unwrap()
+    }
+
+    fn variable(name: &str, value: &str) -> (String, String) {
+        (name.to_owned(), value.to_owned())
+    }
+
+    #[test]
+    fn parse_handles_export_and_comments() {
+        // Act
+        let parsed = variables("# comment\nexport NAME=app # trailing\n\nPORT = 8080\n");
+
+        // Assert
+        assert_eq!(parsed, vec![variable("NAME", "app"), variable("PORT", "8080")]);
+    }
+
+    #[test]
+    fn parse_handles_quoted_values() {
+        // Act
+        let parsed = variables("SINGLE='${HOME} \\n'\nDOUBLE=\"say \\\"hi\\\"\\n\" # comment\n");
+
+        // Assert
+        assert_eq!(parsed, vec![variable("SINGLE", "${HOME} \\n"), variable("DOUBLE", "say \"hi\"\n")]);
+    }
+
+    #[test]
+    fn parse_handles_multi_line_values() {
+        // Act
+        let parsed = variables("KEY=\"-----BEGIN-----\nabc\n-----END-----\"\nNEXT=1\n");
+
+        // Assert
+        assert_eq!(parsed,
+                   vec![variable("KEY", "-----BEGIN-----\nabc\n-----END-----"), variable("NEXT", "1")]);
+    }
+
+    #[test]
+    fn parse_expands_references() {
+        // Act
+        let parsed = variables("DIR=${HOME}/data\nLOG=$DIR/log\nLEVEL=${UNSET:-info}\n");
+
+        // Assert
+        assert_eq!(parsed,
+                   vec![variable("DIR", "/home/app/data"),
+                        variable("LOG", "/home/app/data/log"),
+                        variable("LEVEL", "info")]);
+    }
+
+    #[test]
+    fn parse_unterminated_quote_returns_error() {
+        // Act
+        let error = parse("A=1\nKEY=\"unterminated\n", |_| None).unwrap_err();
+
+        // Assert
+        assert_eq!(error, (2, "unterminated quoted value".to_owned()));
+    }
+}
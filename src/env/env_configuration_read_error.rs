@@ -0,0 +1,13 @@
+quick_error! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum EnvConfigurationReadError {
+        Missing(variable: String) {
+            description("missing environment variable")
+            display("missing environment variable {}", variable)
+        }
+        Invalid(variable: String, message: String) {
+            description("invalid environment variable")
+            display("invalid environment variable {}: {}", variable, message)
+        }
+    }
+}
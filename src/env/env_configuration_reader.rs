@@ -0,0 +1,171 @@
+use ConfigurationReader;
+use super::{EnvConfigurationReadError, EnvKeyMapping};
+use value::ConfigurationValue;
+use futures::IntoFuture;
+use futures::future::FutureResult;
+use serde::de::DeserializeOwned;
+use std::env;
+use std::marker::PhantomData;
+
+/// A `ConfigurationReader` which reads a configuration from the environment variables of the process.
+///
+/// Variables are mapped to nested keys by an `EnvKeyMapping` and then converted into `C`, which by default
+/// is the untyped `ConfigurationValue`.
+#[derive(Debug)]
+pub struct EnvConfigurationReader<C = ConfigurationValue> {
+    mapping: EnvKeyMapping,
+    phantom_data: PhantomData<C>,
+}
+
+impl<C> EnvConfigurationReader<C>
+    where C: DeserializeOwned
+{
+    /// Creates a new `EnvConfigurationReader<C>` for variables starting with the specified prefix.
+    pub fn new<P: Into<String>>(prefix: P) -> Self {
+        Self::with_mapping(EnvKeyMapping::new(prefix))
+    }
+
+    /// Creates a new `EnvConfigurationReader<C>` using the specified `EnvKeyMapping`.
+    pub fn with_mapping(mapping: EnvKeyMapping) -> Self {
+        Self {
+            mapping: mapping,
+            phantom_data: Default::default(),
+        }
+    }
+
+    /// Uses the specified separator between nested keys.
+    pub fn with_separator<S: Into<String>>(self, separator: S) -> Self {
+        Self::with_mapping(self.mapping.with_separator(separator))
+    }
+}
+
+impl<C> EnvConfigurationReader<C> {
+    pub fn mapping(&self) -> &EnvKeyMapping {
+        &self.mapping
+    }
+
+    fn variables(&self) -> Result<Vec<(String, String)>, EnvConfigurationReadError> {
+        let mut variables = Vec::new();
+        for (name, value) in env::vars_os() {
+            // Variables which could never be mapped to a key are ignored
+            let name = match name.into_string() {
+                Ok(name) => name,
+                Err(_) => continue,
+            };
+
+            if self.mapping.key_path(&name).is_none() {
+                continue;
+            }
+
+            match value.into_string() {
+                Ok(value) => variables.push((name, value)),
+                Err(_) => return Err(EnvConfigurationReadError::Invalid(name, "value is not valid unicode".to_owned())),
+            }
+        }
+
+        Ok(variables)
+    }
+}
+
+impl<C> Clone for EnvConfigurationReader<C> {
+    fn clone(&self) -> Self {
+        Self {
+            mapping: self.mapping.clone(),
+            phantom_data: Default::default(),
+        }
+    }
+}
+
+impl<C> ConfigurationReader for EnvConfigurationReader<C>
+    where C: DeserializeOwned + Send + 'static
+{
+    type Configuration = C;
+    type Error = EnvConfigurationReadError;
+    type ReadResult = FutureResult<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        self.variables()
+            .and_then(|variables| {
+                self.mapping
+                    .to_value(variables)
+                    .deserialize_into()
+                    .map_err(|err| self.mapping.read_error(err))
+            })
+            .into_future()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ConfigurationReader;
+    use env::{EnvConfigurationReader, EnvConfigurationReadError};
+    use futures::Future;
+    use std::env;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct DatabaseConfiguration {
+        url: String,
+        pool_size: u32,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct TestConfiguration {
+        database: DatabaseConfiguration,
+        debug: Option<bool>,
+    }
+
+    #[test]
+    fn read_configuration_maps_nested_variables() {
+        // Arrange
+        env::set_var("LZ_ENV_NESTED_DATABASE__URL", "postgres://localhost");
+        env::set_var("LZ_ENV_NESTED_DATABASE__POOL_SIZE", "4");
+        env::set_var("LZ_ENV_NESTED_DEBUG", "true");
+
+        let reader = EnvConfigurationReader::<TestConfiguration>::new("LZ_ENV_NESTED_");
+
+        // Act
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration,
+                   TestConfiguration {
+                       database: DatabaseConfiguration {
+                           url: "postgres://localhost".to_owned(),
+                           pool_size: 4,
+                       },
+                       debug: Some(true),
+                   });
+    }
+
+    #[test]
+    fn read_configuration_missing_variable_returns_missing() {
+        // Arrange
+        env::set_var("LZ_ENV_MISSING_DATABASE__URL", "postgres://localhost");
+
+        let reader = EnvConfigurationReader::<TestConfiguration>::new("LZ_ENV_MISSING_");
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        assert_eq!(error, EnvConfigurationReadError::Missing("LZ_ENV_MISSING_DATABASE__POOL_SIZE".to_owned()));
+    }
+
+    #[test]
+    fn read_configuration_unparsable_variable_returns_invalid() {
+        // Arrange
+        env::set_var("LZ_ENV_INVALID_DATABASE__URL", "postgres://localhost");
+        env::set_var("LZ_ENV_INVALID_DATABASE__POOL_SIZE", "many");
+
+        let reader = EnvConfigurationReader::<TestConfiguration>::new("LZ_ENV_INVALID_");
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        match error {
+            EnvConfigurationReadError::Invalid(ref variable, _) => assert_eq!(variable, "LZ_ENV_INVALID_DATABASE__POOL_SIZE"),
+            other => panic!("expected an invalid variable error, got {:?}", other),
+        }
+    }
+}
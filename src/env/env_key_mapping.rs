@@ -0,0 +1,161 @@
+use super::EnvConfigurationReadError;
+use value::{ConfigurationValue, ConfigurationValueError};
+
+/// Maps environment variable names to nested configuration keys.
+///
+/// Only variables starting with the prefix are mapped, the remainder of the name is split on the
+/// separator and lower-cased, so with a prefix of `APP_` and a separator of `__` the variable
+/// `APP_SERVER__PORT` maps to the key `server.port`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvKeyMapping {
+    prefix: String,
+    separator: String,
+}
+
+impl Default for EnvKeyMapping {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl EnvKeyMapping {
+    /// Creates a new `EnvKeyMapping` for variables starting with the specified prefix, separating nested keys with `__`.
+    pub fn new<P: Into<String>>(prefix: P) -> Self {
+        Self {
+            prefix: prefix.into(),
+            separator: "__".to_owned(),
+        }
+    }
+
+    /// Uses the specified separator between nested keys.
+    pub fn with_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    /// Gets the path of nested keys for the specified variable, this is `None` if the variable is not mapped.
+    pub fn key_path(&self, variable: &str) -> Option<Vec<String>> {
+        if !variable.starts_with(&self.prefix) {
+            return None;
+        }
+
+        let remainder = &variable[self.prefix.len()..];
+        if remainder.is_empty() {
+            return None;
+        }
+
+        let path = if self.separator.is_empty() {
+            vec![remainder.to_lowercase()]
+        } else {
+            remainder.split(self.separator.as_str()).map(str::to_lowercase).collect()
+        };
+
+        if path.iter().any(String::is_empty) {
+            return None;
+        }
+
+        Some(path)
+    }
+
+    /// Gets the name of the variable which maps to the specified path of nested keys.
+    pub fn variable_name<K: AsRef<str>>(&self, path: &[K]) -> String {
+        let keys: Vec<_> = path.iter().map(|key| key.as_ref().to_uppercase()).collect();
+        format!("{}{}", self.prefix, keys.join(&self.separator))
+    }
+
+    /// Builds a `ConfigurationValue` from the mapped variables.
+    ///
+    /// Variables are applied in name order, so a variable which maps to a nested key replaces a variable
+    /// which maps to its parent.
+    pub fn to_value<I, K, V>(&self, variables: I) -> ConfigurationValue
+        where I: IntoIterator<Item = (K, V)>,
+              K: AsRef<str>,
+              V: Into<String>
+    {
+        let mut mapped: Vec<_> = variables.into_iter()
+            .filter_map(|(name, value)| self.key_path(name.as_ref()).map(|path| (path, value.into())))
+            .collect();
+        mapped.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut configuration = ConfigurationValue::table();
+        for (path, value) in mapped {
+            configuration.insert(&path, ConfigurationValue::String(value));
+        }
+
+        configuration
+    }
+
+    /// Converts an error from `ConfigurationValue::deserialize_into` into an error naming the responsible variable.
+    pub fn read_error(&self, error: ConfigurationValueError) -> EnvConfigurationReadError {
+        let variable = self.variable_name(error.path());
+        match error.message() {
+            Some(message) => EnvConfigurationReadError::Invalid(variable, message.to_owned()),
+            None => EnvConfigurationReadError::Missing(variable),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use env::EnvKeyMapping;
+    use value::ConfigurationValue;
+
+    #[test]
+    fn key_path_splits_on_separator() {
+        // Arrange
+        let mapping = EnvKeyMapping::new("APP_");
+
+        // Act
+        let path = mapping.key_path("APP_SERVER__PORT");
+
+        // Assert
+        assert_eq!(path, Some(vec!["server".to_owned(), "port".to_owned()]));
+    }
+
+    #[test]
+    fn key_path_without_prefix_returns_none() {
+        // Arrange
+        let mapping = EnvKeyMapping::new("APP_");
+
+        // Act
+        let path = mapping.key_path("OTHER_SERVER__PORT");
+
+        // Assert
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn variable_name_is_inverse_of_key_path() {
+        // Arrange
+        let mapping = EnvKeyMapping::new("APP_").with_separator("_");
+
+        // Act
+        let name = mapping.variable_name(&["server", "port"]);
+
+        // Assert
+        assert_eq!(name, "APP_SERVER_PORT");
+    }
+
+    #[test]
+    fn to_value_nests_mapped_variables() {
+        // Arrange
+        let mapping = EnvKeyMapping::new("APP_");
+        let variables = vec![("APP_SERVER__PORT", "8080"), ("HOME", "/root")];
+
+        // Act
+        let value = mapping.to_value(variables);
+
+        // Assert
+        let mut expected = ConfigurationValue::table();
+        expected.insert(&["server", "port"], "8080".into());
+        assert_eq!(value, expected);
+    }
+}
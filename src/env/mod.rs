@@ -0,0 +1,16 @@
+mod env_key_mapping;
+pub use self::env_key_mapping::*;
+
+mod env_configuration_read_error;
+pub use self::env_configuration_read_error::*;
+
+mod env_configuration_reader;
+pub use self::env_configuration_reader::*;
+
+mod dotenv_parser;
+
+mod dotenv_configuration_read_error;
+pub use self::dotenv_configuration_read_error::*;
+
+mod dotenv_configuration_reader;
+pub use self::dotenv_configuration_reader::*;
@@ -0,0 +1,162 @@
+use {BoxConfigurationReader, ConfigurationReader};
+use super::{FallbackAttempt, FallbackChainError};
+use futures::{BoxFuture, Future};
+use futures::future::err as err_future;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+/// A `ConfigurationReader` which reads from a list of named sources in order until one succeeds.
+///
+/// Unlike `FallbackConfigurationReader` the sources may be of different types and their number need not be
+/// known at compile time, the error of each source is converted into `E`. When every source fails the error
+/// lists the attempt made with each source.
+pub struct FallbackChain<C, E> {
+    sources: Vec<(String, BoxConfigurationReader<C, E>)>,
+}
+
+impl<C, E> FallbackChain<C, E>
+    where C: Send + 'static,
+          E: Send + 'static
+{
+    /// Creates a new `FallbackChain<C, E>` without any sources.
+    pub fn new() -> Self {
+        Self { sources: Vec::new() }
+    }
+
+    /// Adds a source which is read if every source before it fails.
+    pub fn push<S, R>(&mut self, name: S, reader: R)
+        where S: Into<String>,
+              R: ConfigurationReader<Configuration = C> + Send + Sync + 'static,
+              R::Error: Into<E>
+    {
+        self.sources.push((name.into(), BoxConfigurationReader::new(reader)));
+    }
+
+    /// Adds a source which is read if every source before it fails.
+    pub fn with_source<S, R>(mut self, name: S, reader: R) -> Self
+        where S: Into<String>,
+              R: ConfigurationReader<Configuration = C> + Send + Sync + 'static,
+              R::Error: Into<E>
+    {
+        self.push(name, reader);
+        self
+    }
+}
+
+impl<C, E> FallbackChain<C, E> {
+    /// Gets the names of the sources in the order they are read.
+    pub fn source_names(&self) -> Vec<&str> {
+        self.sources.iter().map(|&(ref name, _)| name.as_str()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+impl<C, E> Clone for FallbackChain<C, E> {
+    fn clone(&self) -> Self {
+        Self { sources: self.sources.clone() }
+    }
+}
+
+impl<C, E> Default for FallbackChain<C, E>
+    where C: Send + 'static,
+          E: Send + 'static
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, E> Debug for FallbackChain<C, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("FallbackChain")
+            .field("sources", &self.source_names())
+            .finish()
+    }
+}
+
+fn read_from<C, E>(sources: Vec<(String, BoxConfigurationReader<C, E>)>,
+                   index: usize,
+                   mut attempts: Vec<FallbackAttempt<E>>)
+                   -> BoxFuture<C, FallbackChainError<E>>
+    where C: Send + 'static,
+          E: Send + 'static
+{
+    let (name, reader) = match sources.get(index) {
+        Some(&(ref name, ref reader)) => (name.clone(), reader.clone()),
+        None => return err_future(FallbackChainError::new(attempts)).boxed(),
+    };
+
+    reader.read()
+        .or_else(move |e| {
+            attempts.push(FallbackAttempt::new(name, e));
+            read_from(sources, index + 1, attempts)
+        })
+        .boxed()
+}
+
+impl<C, E> ConfigurationReader for FallbackChain<C, E>
+    where C: Send + 'static,
+          E: Debug + Send + 'static
+{
+    type Configuration = C;
+    type Error = FallbackChainError<E>;
+    type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        read_from(self.sources.clone(), 0, Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ConfigurationReader;
+    use fallback::FallbackChain;
+    use file::FileConfigurationAccessor;
+    use codec::IniCodec;
+    use memory::MemoryConfigurationAccessor;
+    use value::ConfigurationValue;
+    use futures::Future;
+    use std::error::Error;
+
+    type BoxError = Box<Error + Send + Sync>;
+
+    #[test]
+    fn read_configuration_returns_first_successful_source() {
+        // Arrange
+        let mut configuration = ConfigurationValue::table();
+        configuration.insert(&["name"], "app".into());
+
+        let chain = FallbackChain::<_, BoxError>::new()
+            .with_source("file", FileConfigurationAccessor::new("/nonexistent/app.ini", IniCodec::new()))
+            .with_source("empty", MemoryConfigurationAccessor::empty())
+            .with_source("memory", MemoryConfigurationAccessor::new(configuration.clone()));
+
+        // Act
+        let read = chain.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(read, configuration);
+    }
+
+    #[test]
+    fn read_configuration_all_failed_returns_every_attempt() {
+        // Arrange
+        let mut chain = FallbackChain::<ConfigurationValue, BoxError>::new();
+        for name in &["primary", "secondary"] {
+            chain.push(*name, MemoryConfigurationAccessor::empty());
+        }
+
+        // Act
+        let error = chain.read_configuration().wait().unwrap_err();
+
+        // Assert
+        let sources: Vec<_> = error.attempts().iter().map(|attempt| attempt.source()).collect();
+        assert_eq!(sources, vec!["primary", "secondary"]);
+    }
+}
@@ -0,0 +1,73 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The failed attempt to read from one source of a `FallbackChain`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FallbackAttempt<E> {
+    source: String,
+    error: E,
+}
+
+impl<E> FallbackAttempt<E> {
+    pub fn new<S: Into<String>>(source: S, error: E) -> Self {
+        Self {
+            source: source.into(),
+            error: error,
+        }
+    }
+
+    /// Gets the name of the source which was read from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+}
+
+/// The error when every source of a `FallbackChain` failed, listing each attempt in the order the
+/// sources were read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FallbackChainError<E> {
+    attempts: Vec<FallbackAttempt<E>>,
+}
+
+impl<E> FallbackChainError<E> {
+    pub fn new(attempts: Vec<FallbackAttempt<E>>) -> Self {
+        Self { attempts: attempts }
+    }
+
+    /// Gets every failed attempt, this is empty if the chain had no sources.
+    pub fn attempts(&self) -> &[FallbackAttempt<E>] {
+        &self.attempts
+    }
+
+    pub fn into_attempts(self) -> Vec<FallbackAttempt<E>> {
+        self.attempts
+    }
+}
+
+impl<E: Display> Display for FallbackChainError<E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        if self.attempts.is_empty() {
+            return write!(f, "No configuration sources");
+        }
+
+        for (index, attempt) in self.attempts.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{}: {}", attempt.source, attempt.error)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<E: Error> Error for FallbackChainError<E> {
+    fn description(&self) -> &str {
+        "every configuration source failed"
+    }
+}
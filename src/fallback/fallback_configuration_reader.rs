@@ -26,6 +26,16 @@ impl<R, F> FallbackConfigurationReader<R, F, fn(&R::Error) -> bool>
 }
 
 impl<R, F, P> FallbackConfigurationReader<R, F, P> {
+    /// Gets the reader which is read first.
+    pub fn reader(&self) -> &R {
+        &self.reader
+    }
+
+    /// Gets the reader which is read when the first reader fails.
+    pub fn fallback(&self) -> &F {
+        &self.fallback
+    }
+
     pub fn push_reader_front<NR>(self,
                                  reader: NR)
                                  -> FallbackConfigurationReader<NR, Self, fn(&NR::Error) -> bool>
@@ -70,9 +80,13 @@ impl<R, F, P> FallbackConfigurationReader<R, F, P> {
 
 impl<R, F, P> FallbackConfigurationReader<R, F, P>
     where R: ConfigurationReader,
-          F: ConfigurationReader<Configuration = R::Configuration>,
           P: Fn(&R::Error) -> bool
 {
+    /// Creates a new `FallbackConfigurationReader` which only reads the fallback when `should_fallback`
+    /// returns true for the error of the reader.
+    ///
+    /// The fallback is not required to be a `ConfigurationReader` yet, so the bounds it needs to be read are
+    /// only checked where the `FallbackConfigurationReader` is read.
     pub fn new_conditional(reader: R, fallback: F, should_fallback: P) -> Self {
         Self {
             reader: reader,
@@ -2,4 +2,9 @@ mod fallback_configuration_read_error;
 pub use self::fallback_configuration_read_error::*;
 
 mod fallback_configuration_reader;
-pub use self::fallback_configuration_reader::*;
\ No newline at end of file
+pub use self::fallback_configuration_reader::*;
+mod fallback_chain_error;
+pub use self::fallback_chain_error::*;
+
+mod fallback_chain;
+pub use self::fallback_chain::*;
@@ -0,0 +1,234 @@
+use {ConfigurationAccessor, ConfigurationCodec, ConfigurationReader, ConfigurationWriter};
+use super::{FileConfigurationReadError, FileConfigurationWriteError};
+use futures::IntoFuture;
+use futures::future::FutureResult;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{ErrorKind, Read, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+/// A `ConfigurationReader` and `ConfigurationWriter` which stores a configuration in a file, using a
+/// `ConfigurationCodec` to convert the configuration to and from the contents of the file.
+#[derive(Debug)]
+pub struct FileConfigurationAccessor<C, Codec> {
+    path: PathBuf,
+    codec: Codec,
+    phantom_data: PhantomData<C>,
+}
+
+impl<C, Codec> FileConfigurationAccessor<C, Codec>
+    where Codec: ConfigurationCodec<C>
+{
+    /// Creates a new `FileConfigurationAccessor<C, Codec>` for the file at the specified path.
+    pub fn new<P: Into<PathBuf>>(path: P, codec: Codec) -> Self {
+        Self {
+            path: path.into(),
+            codec: codec,
+            phantom_data: Default::default(),
+        }
+    }
+}
+
+impl<C, Codec> FileConfigurationAccessor<C, Codec> {
+    /// Gets the path of the file containing the configuration.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the codec used to decode and encode the file.
+    pub fn codec(&self) -> &Codec {
+        &self.codec
+    }
+
+    fn read_file(&self) -> Result<Vec<u8>, ::std::io::Error> {
+        let mut file = File::open(&self.path)?;
+
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    fn write_file(&self, bytes: &[u8]) -> Result<(), ::std::io::Error> {
+        // Write to a sibling file first so readers never observe a partially written configuration
+        let mut temp_path = OsString::from(self.path.as_os_str());
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        {
+            let mut file = File::create(&temp_path)?;
+            file.write_all(bytes)?;
+            file.sync_all()?;
+        }
+
+        fs::rename(&temp_path, &self.path)
+    }
+}
+
+impl<C, Codec: Clone> Clone for FileConfigurationAccessor<C, Codec> {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            codec: self.codec.clone(),
+            phantom_data: Default::default(),
+        }
+    }
+}
+
+impl<C, Codec> From<FileConfigurationAccessor<C, Codec>> for ConfigurationAccessor<FileConfigurationAccessor<C, Codec>, FileConfigurationAccessor<C, Codec>>
+    where C: Send + 'static,
+          Codec: ConfigurationCodec<C> + Clone
+{
+    fn from(value: FileConfigurationAccessor<C, Codec>) -> Self {
+        ConfigurationAccessor::new(value.clone(), value)
+    }
+}
+
+impl<C, Codec> ConfigurationReader for FileConfigurationAccessor<C, Codec>
+    where C: Send + 'static,
+          Codec: ConfigurationCodec<C>
+{
+    type Configuration = C;
+    type Error = FileConfigurationReadError<Codec::Error>;
+    type ReadResult = FutureResult<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        self.read_file()
+            .map_err(FileConfigurationReadError::Io)
+            .and_then(|bytes| self.codec.decode(&bytes).map_err(FileConfigurationReadError::Decode))
+            .into_future()
+    }
+}
+
+impl<C, Codec> ConfigurationWriter for FileConfigurationAccessor<C, Codec>
+    where C: Send + 'static,
+          Codec: ConfigurationCodec<C>
+{
+    type Configuration = C;
+    type Error = FileConfigurationWriteError<Codec::Error>;
+    type WriteResult = FutureResult<(), Self::Error>;
+
+    fn write_configuration(&mut self, configuration: &Self::Configuration) -> Self::WriteResult {
+        let encoded = match self.read_file() {
+            Ok(existing) => self.codec.encode_update(configuration, &existing),
+            Err(ref err) if err.kind() == ErrorKind::NotFound => self.codec.encode(configuration),
+            Err(err) => return Err(FileConfigurationWriteError::Io(err)).into_future(),
+        };
+
+        encoded.map_err(FileConfigurationWriteError::Encode)
+            .and_then(|bytes| self.write_file(&bytes).map_err(FileConfigurationWriteError::Io))
+            .into_future()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationCodec, ConfigurationReader, ConfigurationWriter, FluentConfigurationReader};
+    use closure::ClosureConfigurationReader;
+    use file::{FileConfigurationAccessor, FileConfigurationReadError};
+    use futures::Future;
+    use futures::future;
+    use std::fs::File;
+    use std::io::Write;
+    use std::str::{self, Utf8Error};
+    use tempdir::TempDir;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct TestConfiguration(pub String);
+
+    #[derive(Debug, Clone)]
+    struct TestCodec;
+
+    impl ConfigurationCodec<TestConfiguration> for TestCodec {
+        type Error = Utf8Error;
+
+        fn decode(&self, bytes: &[u8]) -> Result<TestConfiguration, Self::Error> {
+            str::from_utf8(bytes).map(|s| TestConfiguration(s.to_owned()))
+        }
+
+        fn encode(&self, configuration: &TestConfiguration) -> Result<Vec<u8>, Self::Error> {
+            Ok(configuration.0.as_bytes().to_vec())
+        }
+    }
+
+    #[test]
+    fn read_configuration_missing_file_returns_not_found() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let accessor = FileConfigurationAccessor::new(directory.path().join("missing"), TestCodec);
+
+        // Act
+        let error = accessor.read_configuration().wait().unwrap_err();
+
+        // Assert
+        assert!(error.is_not_found());
+    }
+
+    #[test]
+    fn read_configuration_decodes_file() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let path = directory.path().join("configuration");
+        File::create(&path).unwrap().write_all(b"hello").unwrap();
+
+        let accessor = FileConfigurationAccessor::new(path, TestCodec);
+
+        // Act
+        let configuration = accessor.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration, TestConfiguration("hello".to_owned()));
+    }
+
+    #[test]
+    fn read_configuration_invalid_file_returns_decode_error() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let path = directory.path().join("configuration");
+        File::create(&path).unwrap().write_all(&[0xff, 0xfe]).unwrap();
+
+        let accessor = FileConfigurationAccessor::new(path, TestCodec);
+
+        // Act
+        let error = accessor.read_configuration().wait().unwrap_err();
+
+        // Assert
+        match error {
+            FileConfigurationReadError::Decode(_) => {}
+            other => panic!("expected a decode error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn read_configuration_after_write_returns_configuration() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let mut accessor = FileConfigurationAccessor::new(directory.path().join("configuration"), TestCodec);
+
+        accessor.write_configuration(&TestConfiguration("written".to_owned())).wait().unwrap();
+
+        // Act
+        let configuration = accessor.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration, TestConfiguration("written".to_owned()));
+    }
+
+    #[test]
+    fn with_cache_writes_configuration_to_file() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let accessor = FileConfigurationAccessor::new(directory.path().join("configuration"), TestCodec);
+
+        let (reader, _) = ClosureConfigurationReader::new(|| future::ok::<_, !>(TestConfiguration("origin".to_owned())))
+            .with_cache(accessor.clone());
+
+        // Act
+        reader.read_configuration().wait().unwrap();
+
+        // Assert
+        let configuration = accessor.read_configuration().wait().unwrap();
+        assert_eq!(configuration, TestConfiguration("origin".to_owned()));
+    }
+}
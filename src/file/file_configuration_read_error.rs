@@ -0,0 +1,44 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::{Error as IoError, ErrorKind};
+
+#[derive(Debug)]
+pub enum FileConfigurationReadError<E> {
+    Io(IoError),
+    Decode(E),
+}
+
+impl<E> FileConfigurationReadError<E> {
+    /// Returns whether this error was caused by the file not existing.
+    pub fn is_not_found(&self) -> bool {
+        match *self {
+            FileConfigurationReadError::Io(ref err) => err.kind() == ErrorKind::NotFound,
+            FileConfigurationReadError::Decode(_) => false,
+        }
+    }
+}
+
+impl<E: Display> Display for FileConfigurationReadError<E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            FileConfigurationReadError::Io(ref err) => write!(f, "Io Error {}", err),
+            FileConfigurationReadError::Decode(ref err) => write!(f, "Decode Error {}", err),
+        }
+    }
+}
+
+impl<E: Error> Error for FileConfigurationReadError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            FileConfigurationReadError::Io(ref err) => err.description(),
+            FileConfigurationReadError::Decode(ref err) => err.description(),
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            FileConfigurationReadError::Io(ref err) => Some(err),
+            FileConfigurationReadError::Decode(ref err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::Error as IoError;
+
+#[derive(Debug)]
+pub enum FileConfigurationWriteError<E> {
+    Io(IoError),
+    Encode(E),
+}
+
+impl<E: Display> Display for FileConfigurationWriteError<E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            FileConfigurationWriteError::Io(ref err) => write!(f, "Io Error {}", err),
+            FileConfigurationWriteError::Encode(ref err) => write!(f, "Encode Error {}", err),
+        }
+    }
+}
+
+impl<E: Error> Error for FileConfigurationWriteError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            FileConfigurationWriteError::Io(ref err) => err.description(),
+            FileConfigurationWriteError::Encode(ref err) => err.description(),
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            FileConfigurationWriteError::Io(ref err) => Some(err),
+            FileConfigurationWriteError::Encode(ref err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,8 @@
+mod file_configuration_read_error;
+pub use self::file_configuration_read_error::*;
+
+mod file_configuration_write_error;
+pub use self::file_configuration_write_error::*;
+
+mod file_configuration_accessor;
+pub use self::file_configuration_accessor::*;
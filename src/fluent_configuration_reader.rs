@@ -1,9 +1,15 @@
 use {ConfigurationAccessor, ConfigurationReader, ConfigurationWriter};
 use fallback::FallbackConfigurationReader;
-use copy_on_read::CopyOnReadConfigurationReader;
+use copy_on_read::{ClearableConfigurationReader, CopyOnReadConfigurationReader, CopyOnReadHandle};
+use map::{AndThenConfigurationReader, FilterMapConfigurationReader, MapConfigurationReader, MapErrConfigurationReader};
 
 /// A trait to fluently build a `ConfigurationReader`.
 pub trait FluentConfigurationReader: ConfigurationReader {
+    /// Caches the configuration of this reader in the accessor, this reader is only read when reading the
+    /// accessor fails.
+    ///
+    /// Returns the reader with a `CopyOnReadHandle` which clears or refreshes the cached configuration.
+    ///
     /// # Examples
     /// ```
     /// #![feature(never_type, integer_atomics)]
@@ -30,27 +36,39 @@ pub trait FluentConfigurationReader: ConfigurationReader {
     ///         future::ok::<TestConfiguration, !>(TestConfiguration)
     ///     });
     ///
-    ///     let configuration_reader = configuration_reader.with_cache(MemoryConfigurationAccessor::empty());
+    ///     let (configuration_reader, cache) = configuration_reader.with_cache(MemoryConfigurationAccessor::empty());
     ///     configuration_reader.read_configuration().wait().unwrap();
     ///     configuration_reader.read_configuration().wait().unwrap();
     ///
     ///     assert_eq!(called_count.load(Ordering::SeqCst), 1, "The result of the first invocation should have been cached");
+    ///
+    ///     cache.clear();
+    ///     configuration_reader.read_configuration().wait().unwrap();
+    ///
+    ///     assert_eq!(called_count.load(Ordering::SeqCst), 2, "The cleared configuration should have been read again");
     /// }
     /// ```
     fn with_cache<A, R, W>(self,
                         accessor: A)
-                        -> FallbackConfigurationReader<R,
-                                                       CopyOnReadConfigurationReader<Self, W>,
-                                                       fn(&R::Error) -> bool>
+                        -> (FallbackConfigurationReader<ClearableConfigurationReader<R>,
+                                                        CopyOnReadConfigurationReader<Self, W>,
+                                                        fn(&Option<R::Error>) -> bool>,
+                            CopyOnReadHandle<Self, W>)
         where Self: Sized + 'static,
+            Self::Configuration: Clone + Sync,
+            Self::Error: Sync,
             A: Into<ConfigurationAccessor<R, W>>,
             R: ConfigurationReader<Configuration = Self::Configuration>,
-            W: ConfigurationWriter<Configuration = Self::Configuration> + Send + 'static
+            W: ConfigurationWriter<Configuration = Self::Configuration> + Send + 'static,
+            W::Error: Sync
     {
         let (reader, writer) = accessor.into().into();
 
         let copy_configuration_reader = CopyOnReadConfigurationReader::new(self, writer);
-        FallbackConfigurationReader::new(reader, copy_configuration_reader)
+        let reader = ClearableConfigurationReader::new(reader, &copy_configuration_reader);
+        let handle = CopyOnReadHandle::new(copy_configuration_reader.clone());
+
+        (FallbackConfigurationReader::new(reader, copy_configuration_reader), handle)
     }
 
     fn with_conditional_cache<A, R, W, P>
@@ -71,6 +89,38 @@ pub trait FluentConfigurationReader: ConfigurationReader {
                                                      copy_configuration_reader,
                                                      should_fallback)
     }
+
+    /// Transforms each configuration which is read with `map`.
+    fn map<F, U>(self, map: F) -> MapConfigurationReader<Self, F>
+        where Self: Sized,
+              F: Fn(Self::Configuration) -> U
+    {
+        MapConfigurationReader::new(self, map)
+    }
+
+    /// Transforms each configuration which is read with `and_then`, failing when the transform fails.
+    fn and_then<F, U, E>(self, and_then: F) -> AndThenConfigurationReader<Self, F>
+        where Self: Sized,
+              F: Fn(Self::Configuration) -> Result<U, E>
+    {
+        AndThenConfigurationReader::new(self, and_then)
+    }
+
+    /// Transforms each configuration which is read with `filter_map`, failing when it returns `None`.
+    fn filter_map<F, U>(self, filter_map: F) -> FilterMapConfigurationReader<Self, F>
+        where Self: Sized,
+              F: Fn(Self::Configuration) -> Option<U>
+    {
+        FilterMapConfigurationReader::new(self, filter_map)
+    }
+
+    /// Transforms each error of the reader with `map_err`.
+    fn map_err<F, E>(self, map_err: F) -> MapErrConfigurationReader<Self, F>
+        where Self: Sized,
+              F: Fn(Self::Error) -> E
+    {
+        MapErrConfigurationReader::new(self, map_err)
+    }
 }
 
 impl<R> FluentConfigurationReader for R where R: ConfigurationReader {}
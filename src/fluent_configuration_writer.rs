@@ -0,0 +1,15 @@
+use ConfigurationWriter;
+use map::ContramapConfigurationWriter;
+
+/// A trait to fluently build a `ConfigurationWriter`.
+pub trait FluentConfigurationWriter: ConfigurationWriter {
+    /// Transforms each configuration with `contramap` before it is written.
+    fn contramap<F, C>(self, contramap: F) -> ContramapConfigurationWriter<Self, F, C>
+        where Self: Sized,
+              F: Fn(&C) -> Self::Configuration
+    {
+        ContramapConfigurationWriter::new(self, contramap)
+    }
+}
+
+impl<W> FluentConfigurationWriter for W where W: ConfigurationWriter {}
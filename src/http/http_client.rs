@@ -0,0 +1,282 @@
+use std::io::{BufRead, BufReader, Error as IoError, ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// The parts of an `http` URL needed to make a request, the host of an IPv6 address is kept without its
+/// brackets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct HttpUrl {
+    pub host: String,
+    pub port: u16,
+    pub path: String,
+}
+
+impl HttpUrl {
+    /// Parses a URL of the form `http://host[:port][/path][?query]`, where the host may be a bracketed IPv6
+    /// address such as `[::1]`, `https` is not supported.
+    pub fn parse(url: &str) -> Option<Self> {
+        let remainder = match url.find("://") {
+            Some(index) if url[..index].eq_ignore_ascii_case("http") => &url[index + 3..],
+            _ => return None,
+        };
+
+        let (authority, path) = match remainder.find(|c| c == '/' || c == '?') {
+            Some(index) => (&remainder[..index], &remainder[index..]),
+            None => (remainder, "/"),
+        };
+
+        let (host, port) = if authority.starts_with('[') {
+            let end = authority.find(']')?;
+            let port = match &authority[end + 1..] {
+                "" => 80,
+                port if port.starts_with(':') => port[1..].parse().ok()?,
+                _ => return None,
+            };
+            (&authority[1..end], port)
+        } else {
+            match authority.rfind(':') {
+                Some(index) => (&authority[..index], authority[index + 1..].parse().ok()?),
+                None => (authority, 80),
+            }
+        };
+
+        if host.is_empty() {
+            return None;
+        }
+
+        let path = if path.starts_with('?') { format!("/{}", path) } else { path.to_owned() };
+        let path = match path.find('#') {
+            Some(fragment) => path[..fragment].to_owned(),
+            None => path,
+        };
+
+        Some(Self {
+            host: host.to_owned(),
+            port: port,
+            path: path,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Gets the value of the first header with the specified name, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|&&(ref header, _)| header.eq_ignore_ascii_case(name))
+            .map(|&(_, ref value)| value.as_str())
+    }
+}
+
+fn protocol_error<S: Into<String>>(message: S) -> IoError {
+    IoError::new(ErrorKind::InvalidData, message.into())
+}
+
+/// Connects to each of the addresses the host resolves to in turn, returning the first connection made.
+fn connect(url: &HttpUrl, timeout: Option<Duration>) -> Result<TcpStream, IoError> {
+    let mut last_error = None;
+
+    for address in (url.host.as_str(), url.port).to_socket_addrs()? {
+        let connected = match timeout {
+            Some(timeout) => TcpStream::connect_timeout(&address, timeout),
+            None => TcpStream::connect(address),
+        };
+
+        match connected {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last_error = Some(err),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| IoError::new(ErrorKind::NotFound, format!("'{}' did not resolve to an address", url.host))))
+}
+
+/// Makes an HTTP/1.1 `GET` request with the additional headers, the connection is closed after the response.
+pub(super) fn get(url: &HttpUrl, headers: &[(&str, &str)], timeout: Option<Duration>) -> Result<HttpResponse, IoError> {
+    let mut stream = connect(url, timeout)?;
+    stream.set_read_timeout(timeout)?;
+    stream.set_write_timeout(timeout)?;
+
+    let mut request = if url.host.contains(':') {
+        format!("GET {} HTTP/1.1\r\nHost: [{}]", url.path, url.host)
+    } else {
+        format!("GET {} HTTP/1.1\r\nHost: {}", url.path, url.host)
+    };
+    if url.port != 80 {
+        request.push_str(&format!(":{}", url.port));
+    }
+    request.push_str("\r\nConnection: close\r\nAccept-Encoding: identity\r\n");
+    for &(name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    request.push_str("\r\n");
+
+    stream.write_all(request.as_bytes())?;
+    stream.flush()?;
+
+    read_response(BufReader::new(stream))
+}
+
+fn read_line<R: BufRead>(reader: &mut R) -> Result<String, IoError> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(protocol_error("unexpected end of response"));
+    }
+
+    Ok(line.trim_end_matches(|c| c == '\r' || c == '\n').to_owned())
+}
+
+fn read_response<R: BufRead>(mut reader: R) -> Result<HttpResponse, IoError> {
+    let status_line = read_line(&mut reader)?;
+    let mut parts = status_line.splitn(3, ' ');
+    let status = match (parts.next(), parts.next()) {
+        (Some(version), Some(status)) if version.starts_with("HTTP/") => status.parse().ok(),
+        _ => None,
+    };
+    let status = status.ok_or_else(|| protocol_error(format!("invalid status line '{}'", status_line)))?;
+
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(&mut reader)?;
+        if line.is_empty() {
+            break;
+        }
+
+        match line.find(':') {
+            Some(index) => headers.push((line[..index].trim().to_owned(), line[index + 1..].trim().to_owned())),
+            None => return Err(protocol_error(format!("invalid header '{}'", line))),
+        }
+    }
+
+    let mut response = HttpResponse {
+        status: status,
+        headers: headers,
+        body: Vec::new(),
+    };
+
+    // Informational and not modified responses never have a body
+    if status < 200 || status == 204 || status == 304 {
+        return Ok(response);
+    }
+
+    let chunked = response.header("Transfer-Encoding").map_or(false, |encoding| encoding.eq_ignore_ascii_case("chunked"));
+    if chunked {
+        response.body = read_chunked(&mut reader)?;
+    } else if let Some(length) = response.header("Content-Length") {
+        let length: u64 = length.parse().map_err(|_| protocol_error(format!("invalid content length '{}'", length)))?;
+        reader.take(length).read_to_end(&mut response.body)?;
+        if response.body.len() as u64 != length {
+            return Err(protocol_error("unexpected end of response"));
+        }
+    } else {
+        reader.read_to_end(&mut response.body)?;
+    }
+
+    Ok(response)
+}
+
+fn read_chunked<R: BufRead>(reader: &mut R) -> Result<Vec<u8>, IoError> {
+    let mut body = Vec::new();
+    loop {
+        let line = read_line(reader)?;
+        let size = line.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size, 16).map_err(|_| protocol_error(format!("invalid chunk size '{}'", size)))?;
+
+        if size == 0 {
+            // Skip any trailers up to the final empty line
+            while !read_line(reader)?.is_empty() {}
+            return Ok(body);
+        }
+
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..])?;
+        read_line(reader)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HttpUrl, read_response};
+
+    #[test]
+    fn parse_url_splits_host_port_and_path() {
+        // Act
+        let url = HttpUrl::parse("http://config.local:8500/v1/app?raw=true").unwrap();
+
+        // Assert
+        assert_eq!(url,
+                   HttpUrl {
+                       host: "config.local".to_owned(),
+                       port: 8500,
+                       path: "/v1/app?raw=true".to_owned(),
+                   });
+    }
+
+    #[test]
+    fn parse_url_ipv6_host_with_port_strips_brackets() {
+        // Act
+        let url = HttpUrl::parse("http://[::1]:8080/").unwrap();
+
+        // Assert
+        assert_eq!(url,
+                   HttpUrl {
+                       host: "::1".to_owned(),
+                       port: 8080,
+                       path: "/".to_owned(),
+                   });
+    }
+
+    #[test]
+    fn parse_url_ipv6_host_without_port_uses_default_port() {
+        // Act
+        let url = HttpUrl::parse("http://[::1]/app").unwrap();
+
+        // Assert
+        assert_eq!(url,
+                   HttpUrl {
+                       host: "::1".to_owned(),
+                       port: 80,
+                       path: "/app".to_owned(),
+                   });
+    }
+
+    #[test]
+    fn parse_url_unclosed_ipv6_host_is_rejected() {
+        // Act
+        let url = HttpUrl::parse("http://[::1:8080/");
+
+        // Assert
+        assert_eq!(url, None);
+    }
+
+    #[test]
+    fn parse_url_rejects_other_schemes() {
+        // Act
+        let url = HttpUrl::parse("https://config.local/app");
+
+        // Assert
+        assert_eq!(url, None);
+    }
+
+    #[test]
+    fn read_response_decodes_chunked_body() {
+        // Arrange
+        let response = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\nETag: \"v1\"\r\n\r\n4\r\nname\r\n3\r\n=ok\r\n0\r\n\r\n";
+
+        // Act
+        let response = read_response(&response[..]).unwrap();
+
+        // Assert
+        assert_eq!(response.status, 200);
+        assert_eq!(response.header("etag"), Some("\"v1\""));
+        assert_eq!(response.body, b"name=ok".to_vec());
+    }
+}
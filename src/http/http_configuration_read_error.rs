@@ -0,0 +1,47 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::Error as IoError;
+
+#[derive(Debug)]
+pub enum HttpConfigurationReadError<E> {
+    /// The URL is not a valid `http` URL.
+    Url(String),
+    Io(IoError),
+    /// The response was not valid HTTP.
+    Protocol(String),
+    /// The server responded with a status other than `200 OK` or `304 Not Modified`.
+    Status(u16),
+    Decode(E),
+}
+
+impl<E: Display> Display for HttpConfigurationReadError<E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            HttpConfigurationReadError::Url(ref url) => write!(f, "Invalid Url {}", url),
+            HttpConfigurationReadError::Io(ref err) => write!(f, "Io Error {}", err),
+            HttpConfigurationReadError::Protocol(ref message) => write!(f, "Protocol Error {}", message),
+            HttpConfigurationReadError::Status(status) => write!(f, "Unexpected Status {}", status),
+            HttpConfigurationReadError::Decode(ref err) => write!(f, "Decode Error {}", err),
+        }
+    }
+}
+
+impl<E: Error> Error for HttpConfigurationReadError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            HttpConfigurationReadError::Url(_) => "invalid url",
+            HttpConfigurationReadError::Io(ref err) => err.description(),
+            HttpConfigurationReadError::Protocol(_) => "invalid http response",
+            HttpConfigurationReadError::Status(_) => "unexpected http status",
+            HttpConfigurationReadError::Decode(ref err) => err.description(),
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            HttpConfigurationReadError::Io(ref err) => Some(err),
+            HttpConfigurationReadError::Decode(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use {ConfigurationCodec, ConfigurationReader};
+use super::HttpConfigurationReadError;
+use super::http_client::{self, HttpUrl};
+use futures::{BoxFuture, Future};
+use futures::future;
+use std::io::ErrorKind;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How long connecting, sending or receiving may take before a request fails, unless specified otherwise.
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug)]
+struct HttpDocument<C> {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    configuration: C,
+}
+
+/// A `ConfigurationReader` which fetches a configuration from an `http` URL, using a `ConfigurationCodec`
+/// to decode the body of the response.
+///
+/// The `ETag` and `Last-Modified` of the last response are kept and sent with the next request, so when
+/// the server responds with `304 Not Modified` the previously decoded configuration is returned without
+/// downloading or decoding it again. Clones of a reader share the previously fetched configuration.
+///
+/// The request is made when the future returned by `read_configuration` is first polled, blocking the thread
+/// polling it until the response is received, and only plain `http` is supported. Requests
+/// time out after 30 seconds unless another timeout is specified with `with_timeout`.
+#[derive(Debug)]
+pub struct HttpConfigurationReader<C, Codec> {
+    url: String,
+    codec: Arc<Codec>,
+    timeout: Option<Duration>,
+    document: Arc<Mutex<Option<HttpDocument<C>>>>,
+}
+
+impl<C, Codec> HttpConfigurationReader<C, Codec>
+    where Codec: ConfigurationCodec<C>
+{
+    /// Creates a new `HttpConfigurationReader<C, Codec>` for the configuration at the specified URL.
+    pub fn new<S: Into<String>>(url: S, codec: Codec) -> Self {
+        Self {
+            url: url.into(),
+            codec: Arc::new(codec),
+            timeout: Some(Duration::from_secs(DEFAULT_TIMEOUT_SECS)),
+            document: Default::default(),
+        }
+    }
+
+    /// Fails a request if connecting, sending or receiving takes longer than the timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Waits for as long as connecting, sending or receiving takes, a request never times out.
+    pub fn without_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+}
+
+impl<C, Codec> HttpConfigurationReader<C, Codec> {
+    /// Gets the URL the configuration is fetched from.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Gets the codec used to decode the body of a response.
+    pub fn codec(&self) -> &Codec {
+        &self.codec
+    }
+
+    /// Gets how long connecting, sending or receiving may take before a request fails.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+impl<C, Codec> Clone for HttpConfigurationReader<C, Codec> {
+    fn clone(&self) -> Self {
+        Self {
+            url: self.url.clone(),
+            codec: self.codec.clone(),
+            timeout: self.timeout,
+            document: self.document.clone(),
+        }
+    }
+}
+
+impl<C, Codec> HttpConfigurationReader<C, Codec>
+    where C: Clone,
+          Codec: ConfigurationCodec<C>
+{
+    fn fetch(&self) -> Result<C, HttpConfigurationReadError<Codec::Error>> {
+        let url = HttpUrl::parse(&self.url).ok_or_else(|| HttpConfigurationReadError::Url(self.url.clone()))?;
+
+        // The validators are copied so the lock is not held while the request is made.
+        let (etag, last_modified) = match *self.document.lock().unwrap() {
+            Some(ref previous) => (previous.etag.clone(), previous.last_modified.clone()),
+            None => (None, None),
+        };
+
+        let response = {
+            let mut headers = Vec::new();
+            if let Some(ref etag) = etag {
+                headers.push(("If-None-Match", etag.as_str()));
+            }
+            if let Some(ref last_modified) = last_modified {
+                headers.push(("If-Modified-Since", last_modified.as_str()));
+            }
+
+            http_client::get(&url, &headers, self.timeout).map_err(|err| match err.kind() {
+                    ErrorKind::InvalidData => HttpConfigurationReadError::Protocol(err.to_string()),
+                    _ => HttpConfigurationReadError::Io(err),
+                })?
+        };
+
+        match response.status {
+            200 => {
+                let configuration = self.codec.decode(&response.body).map_err(HttpConfigurationReadError::Decode)?;
+
+                *self.document.lock().unwrap() = Some(HttpDocument {
+                    etag: response.header("ETag").map(str::to_owned),
+                    last_modified: response.header("Last-Modified").map(str::to_owned),
+                    configuration: configuration.clone(),
+                });
+
+                Ok(configuration)
+            }
+            304 => {
+                match *self.document.lock().unwrap() {
+                    Some(ref document) => Ok(document.configuration.clone()),
+                    None => Err(HttpConfigurationReadError::Status(304)),
+                }
+            }
+            status => Err(HttpConfigurationReadError::Status(status)),
+        }
+    }
+}
+
+impl<C, Codec> ConfigurationReader for HttpConfigurationReader<C, Codec>
+    where C: Clone + Send + 'static,
+          Codec: ConfigurationCodec<C> + Send + Sync + 'static
+{
+    type Configuration = C;
+    type Error = HttpConfigurationReadError<Codec::Error>;
+    type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        let reader = self.clone();
+        future::lazy(move || reader.fetch()).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationCodec, ConfigurationReader};
+    use codec::{IniCodec, IniCodecError};
+    use http::{HttpConfigurationReadError, HttpConfigurationReader};
+    use value::ConfigurationValue;
+    use futures::Future;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+    use std::time::Duration;
+
+    #[derive(Debug, Clone, Default)]
+    struct CountingCodec {
+        decoded: Arc<AtomicUsize>,
+    }
+
+    impl ConfigurationCodec<ConfigurationValue> for CountingCodec {
+        type Error = IniCodecError;
+
+        fn decode(&self, bytes: &[u8]) -> Result<ConfigurationValue, Self::Error> {
+            self.decoded.fetch_add(1, Ordering::SeqCst);
+            IniCodec::new().decode(bytes)
+        }
+
+        fn encode(&self, configuration: &ConfigurationValue) -> Result<Vec<u8>, Self::Error> {
+            IniCodec::new().encode(configuration)
+        }
+    }
+
+    /// Serves each of the responses to one connection in turn, sending the headers of every request received.
+    fn serve(responses: Vec<&'static str>) -> (String, Receiver<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/app.ini", listener.local_addr().unwrap());
+
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let headers: Vec<String> = BufReader::new(stream.try_clone().unwrap())
+                .lines()
+                .map(|line| line.unwrap())
+                .take_while(|line| !line.is_empty())
+                .collect();
+            sender.send(headers).unwrap();
+
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+
+        (url, receiver)
+    }
+
+    const OK: &'static str = "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nLast-Modified: Tue, 01 Jan 2019 00:00:00 GMT\r\nContent-Length: 12\r\n\r\nname = fleet";
+    const NOT_MODIFIED: &'static str = "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\n\r\n";
+
+    #[test]
+    fn read_configuration_decodes_response() {
+        // Arrange
+        let (url, _requests) = serve(vec![OK]);
+        let reader = HttpConfigurationReader::new(url, IniCodec::new());
+
+        // Act
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration.get(&["name"]), Some(&ConfigurationValue::from("fleet")));
+    }
+
+    #[test]
+    fn read_configuration_not_modified_reuses_previous_configuration() {
+        // Arrange
+        let (url, requests) = serve(vec![OK, NOT_MODIFIED]);
+        let codec = CountingCodec::default();
+        let reader = HttpConfigurationReader::new(url, codec.clone());
+
+        let first = reader.read_configuration().wait().unwrap();
+
+        // Act
+        let second = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(second, first);
+        assert_eq!(codec.decoded.load(Ordering::SeqCst), 1);
+
+        let conditional = requests.iter().nth(1).unwrap();
+        assert!(conditional.contains(&"If-None-Match: \"v1\"".to_owned()));
+        assert!(conditional.contains(&"If-Modified-Since: Tue, 01 Jan 2019 00:00:00 GMT".to_owned()));
+    }
+
+    #[test]
+    fn read_configuration_requests_once_polled() {
+        // Arrange
+        let (url, requests) = serve(vec![OK]);
+        let reader = HttpConfigurationReader::new(url, IniCodec::new());
+
+        // Act
+        let read = reader.read_configuration();
+        let requested_before_poll = requests.recv_timeout(Duration::from_millis(50)).is_ok();
+        read.wait().unwrap();
+
+        // Assert
+        assert!(!requested_before_poll);
+        assert!(requests.recv().is_ok());
+    }
+
+    #[test]
+    fn read_configuration_error_status_returns_status() {
+        // Arrange
+        let (url, _requests) = serve(vec!["HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n"]);
+        let reader = HttpConfigurationReader::new(url, IniCodec::new());
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        match error {
+            HttpConfigurationReadError::Status(503) => {}
+            other => panic!("expected status 503, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn new_times_out_by_default() {
+        // Arrange
+        let reader = HttpConfigurationReader::<ConfigurationValue, _>::new("http://localhost/app.ini", IniCodec::new());
+
+        // Act
+        let timeout = reader.timeout();
+
+        // Assert
+        assert_eq!(timeout, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn read_configuration_unresponsive_server_times_out() {
+        // Arrange
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/app.ini", listener.local_addr().unwrap());
+        let reader = HttpConfigurationReader::new(url, IniCodec::new()).with_timeout(Duration::from_millis(50));
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        match error {
+            HttpConfigurationReadError::Io(_) => {}
+            other => panic!("expected an io error, got {:?}", other),
+        }
+        drop(listener);
+    }
+}
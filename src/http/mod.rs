@@ -0,0 +1,7 @@
+mod http_client;
+
+mod http_configuration_read_error;
+pub use self::http_configuration_read_error::*;
+
+mod http_configuration_reader;
+pub use self::http_configuration_reader::*;
@@ -0,0 +1,264 @@
+use {ConfigurationReader, ConfigurationWriter};
+use super::{LensParent, LensWriteError};
+use futures::{BoxFuture, Future};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::Arc;
+
+/// A `ConfigurationReader` and `ConfigurationWriter` of a section of the configuration of a `LensParent`.
+///
+/// The section is read from the parent configuration with `get`. Writing the section reads the parent
+/// configuration, replaces the section with `set` and writes the parent configuration back during the turn of
+/// the write in the parent, so no other write to the parent happens in between.
+pub struct LensAccessor<A, G, S> {
+    parent: LensParent<A>,
+    get: Arc<G>,
+    set: Arc<S>,
+}
+
+impl<A, G, S, C> LensAccessor<A, G, S>
+    where A: ConfigurationReader,
+          G: Fn(&A::Configuration) -> C,
+          S: Fn(&mut A::Configuration, &C)
+{
+    pub fn new(parent: LensParent<A>, get: G, set: S) -> Self {
+        Self {
+            parent: parent,
+            get: Arc::new(get),
+            set: Arc::new(set),
+        }
+    }
+}
+
+impl<A, G, S> LensAccessor<A, G, S> {
+    pub fn parent(&self) -> &LensParent<A> {
+        &self.parent
+    }
+}
+
+impl<A, G, S> Clone for LensAccessor<A, G, S> {
+    fn clone(&self) -> Self {
+        Self {
+            parent: self.parent.clone(),
+            get: self.get.clone(),
+            set: self.set.clone(),
+        }
+    }
+}
+
+impl<A: Debug, G, S> Debug for LensAccessor<A, G, S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("LensAccessor")
+            .field("parent", &self.parent)
+            .finish()
+    }
+}
+
+impl<A, G, S, C> ConfigurationReader for LensAccessor<A, G, S>
+    where A: ConfigurationReader,
+          G: Fn(&A::Configuration) -> C + Send + Sync + 'static,
+          C: Send + 'static
+{
+    type Configuration = C;
+    type Error = A::Error;
+    type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        let get = self.get.clone();
+
+        self.parent
+            .read_configuration()
+            .map(move |configuration| get(&configuration))
+            .boxed()
+    }
+}
+
+impl<A, G, S, C> ConfigurationWriter for LensAccessor<A, G, S>
+    where A: ConfigurationReader + ConfigurationWriter<Configuration = <A as ConfigurationReader>::Configuration> + Send + 'static,
+          G: Fn(&<A as ConfigurationReader>::Configuration) -> C,
+          S: Fn(&mut <A as ConfigurationReader>::Configuration, &C) + Send + Sync + 'static,
+          C: Clone + Send + 'static
+{
+    type Configuration = C;
+    type Error = LensWriteError<<A as ConfigurationReader>::Error, <A as ConfigurationWriter>::Error>;
+    type WriteResult = BoxFuture<(), Self::Error>;
+
+    fn write_configuration(&mut self, configuration: &Self::Configuration) -> Self::WriteResult {
+        let set = self.set.clone();
+        let configuration = configuration.clone();
+
+        self.parent.modify(move |parent_configuration| set(parent_configuration, &configuration))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationAccessor, ConfigurationReader, ConfigurationWriter};
+    use closure::ClosureConfigurationWriter;
+    use lens::{LensParent, LensWriteError};
+    use memory::{MemoryConfigurationAccessor, MemoryConfigurationReadError};
+    use futures::Future;
+    use futures::executor::{self, Notify};
+    use futures::sync::oneshot;
+    use std::panic::{self, AssertUnwindSafe};
+
+    struct NotifyNothing;
+
+    impl Notify for NotifyNothing {
+        fn notify(&self, _: usize) {}
+    }
+
+    static NOTIFY_NOTHING: &'static NotifyNothing = &NotifyNothing;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct AppConfiguration {
+        host: String,
+        port: u16,
+    }
+
+    fn app_configuration() -> AppConfiguration {
+        AppConfiguration {
+            host: "localhost".to_owned(),
+            port: 8080,
+        }
+    }
+
+    fn get_host(app: &AppConfiguration) -> String {
+        app.host.clone()
+    }
+
+    fn set_host(app: &mut AppConfiguration, host: &String) {
+        app.host = host.clone();
+    }
+
+    #[test]
+    fn read_configuration_returns_section() {
+        // Arrange
+        let parent = LensParent::new(MemoryConfigurationAccessor::new(app_configuration()));
+
+        // Act
+        let configuration = parent.lens(get_host, set_host).read_configuration().wait();
+
+        // Assert
+        assert_eq!(configuration, Ok("localhost".to_owned()));
+    }
+
+    #[test]
+    fn write_configuration_writes_section_of_parent() {
+        // Arrange
+        let memory = MemoryConfigurationAccessor::new(app_configuration());
+        let parent = LensParent::new(memory.clone());
+        let mut port = parent.lens(|app: &AppConfiguration| app.port,
+                                   |app: &mut AppConfiguration, port: &u16| app.port = *port);
+
+        // Act
+        port.write_configuration(&9090).wait().unwrap();
+
+        // Assert
+        assert_eq!(memory.read_configuration().wait().unwrap(),
+                   AppConfiguration {
+                       host: "localhost".to_owned(),
+                       port: 9090,
+                   });
+    }
+
+    #[test]
+    fn write_configuration_concurrent_sections_are_both_written() {
+        // Arrange
+        let parent = LensParent::new(MemoryConfigurationAccessor::new(app_configuration()));
+        let mut host = parent.lens(get_host, set_host);
+        let mut port = parent.lens(|app: &AppConfiguration| app.port,
+                                   |app: &mut AppConfiguration, port: &u16| app.port = *port);
+
+        // Act
+        let host_write = host.write_configuration(&"example.com".to_owned());
+        let port_write = port.write_configuration(&9090);
+        host_write.join(port_write).wait().unwrap();
+
+        // Assert
+        assert_eq!(parent.read_configuration().wait().unwrap(),
+                   AppConfiguration {
+                       host: "example.com".to_owned(),
+                       port: 9090,
+                   });
+    }
+
+    #[test]
+    fn write_configuration_empty_parent_returns_read_error() {
+        // Arrange
+        let parent = LensParent::new(MemoryConfigurationAccessor::<AppConfiguration>::empty());
+        let mut port = parent.lens(|app: &AppConfiguration| app.port,
+                                   |app: &mut AppConfiguration, port: &u16| app.port = *port);
+
+        // Act
+        let error = port.write_configuration(&9090).wait().unwrap_err();
+
+        // Assert
+        assert_eq!(error, LensWriteError::ReadError(MemoryConfigurationReadError::NoConfiguration));
+    }
+
+    #[test]
+    fn write_configuration_panicking_set_does_not_block_later_writes() {
+        // Arrange
+        let mut parent = LensParent::new(MemoryConfigurationAccessor::new(app_configuration()));
+        let mut host = parent.lens(get_host, |_: &mut AppConfiguration, _: &String| panic!("set failed"));
+        let mut port = parent.lens(|app: &AppConfiguration| app.port,
+                                   |app: &mut AppConfiguration, port: &u16| app.port = *port);
+
+        // Act
+        let panicked = panic::catch_unwind(AssertUnwindSafe(|| host.write_configuration(&"example.com".to_owned()).wait()));
+        port.write_configuration(&9090).wait().unwrap();
+        parent.write_configuration(&app_configuration()).wait().unwrap();
+
+        // Assert
+        assert!(panicked.is_err());
+        assert_eq!(parent.read_configuration().wait().unwrap(), app_configuration());
+    }
+
+    #[test]
+    fn write_configuration_waiting_write_runs_after_earlier_write() {
+        // Arrange
+        let (complete, completed) = oneshot::channel::<()>();
+        let completed = completed.shared();
+        let parent = LensParent::new(ConfigurationAccessor::new(MemoryConfigurationAccessor::new(app_configuration()),
+                                                                ClosureConfigurationWriter::new(move |_: &AppConfiguration| {
+                                                                    completed.clone().map(|_| ()).map_err(|_| ())
+                                                                })));
+        let mut host = parent.lens(get_host, set_host);
+        let mut port = parent.lens(|app: &AppConfiguration| app.port,
+                                   |app: &mut AppConfiguration, port: &u16| app.port = *port);
+
+        // Act
+        let mut host_write = executor::spawn(host.write_configuration(&"example.com".to_owned()));
+        let mut port_write = executor::spawn(port.write_configuration(&9090));
+        let host_waiting = host_write.poll_future_notify(&NOTIFY_NOTHING, 0).unwrap().is_not_ready();
+        let port_waiting = port_write.poll_future_notify(&NOTIFY_NOTHING, 0).unwrap().is_not_ready();
+        complete.send(()).unwrap();
+
+        // Assert
+        assert!(host_waiting && port_waiting);
+        assert_eq!(host_write.wait_future(), Ok(()));
+        assert_eq!(port_write.wait_future(), Ok(()));
+    }
+
+    #[test]
+    fn write_configuration_unpolled_write_does_not_block_later_writes() {
+        // Arrange
+        let mut parent = LensParent::new(MemoryConfigurationAccessor::new(app_configuration()));
+        let mut host = parent.lens(get_host, set_host);
+        let mut port = parent.lens(|app: &AppConfiguration| app.port,
+                                   |app: &mut AppConfiguration, port: &u16| app.port = *port);
+
+        // Act
+        let _pending = host.write_configuration(&"example.com".to_owned());
+        port.write_configuration(&9090).wait().unwrap();
+        parent.write_configuration(&app_configuration()).wait().unwrap();
+        port.write_configuration(&9191).wait().unwrap();
+
+        // Assert
+        assert_eq!(parent.read_configuration().wait().unwrap(),
+                   AppConfiguration {
+                       host: "localhost".to_owned(),
+                       port: 9191,
+                   });
+    }
+}
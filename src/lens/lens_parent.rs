@@ -0,0 +1,128 @@
+use {ConfigurationReader, ConfigurationWriter};
+use super::{LensAccessor, LensWriteError};
+use super::write_queue::WriteQueue;
+use futures::{BoxFuture, Future};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+fn lock<A>(accessor: &Mutex<A>) -> MutexGuard<A> {
+    // The lock is only held to start a read or write, should it panic the accessor is still usable
+    accessor.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Shares a configuration accessor between `LensAccessor`s which each read and write a section of its configuration.
+///
+/// Writes made through the parent or any of its lenses take turns, a write waits for the writes before it to
+/// complete without blocking its thread. So a lens writing its section never loses a concurrent write of another
+/// section, for this the accessor must not be written to other than through the parent. A write only asks for its
+/// turn once it is polled.
+#[derive(Debug)]
+pub struct LensParent<A> {
+    accessor: Arc<Mutex<A>>,
+    writes: WriteQueue,
+}
+
+impl<A> LensParent<A> {
+    /// Creates a new `LensParent<A>` sharing the specified accessor.
+    pub fn new(accessor: A) -> Self {
+        Self {
+            accessor: Arc::new(Mutex::new(accessor)),
+            writes: WriteQueue::new(),
+        }
+    }
+
+    /// Creates a `LensAccessor` which reads a section of the configuration with `get` and writes it with `set`.
+    pub fn lens<G, S, C>(&self, get: G, set: S) -> LensAccessor<A, G, S>
+        where A: ConfigurationReader,
+              G: Fn(&A::Configuration) -> C,
+              S: Fn(&mut A::Configuration, &C)
+    {
+        LensAccessor::new(self.clone(), get, set)
+    }
+
+    /// Reads the configuration, changes it with `modify` and writes it back, without any other write happening
+    /// in between.
+    pub(super) fn modify<F>(&self,
+                            modify: F)
+                            -> BoxFuture<(), LensWriteError<<A as ConfigurationReader>::Error, <A as ConfigurationWriter>::Error>>
+        where A: ConfigurationReader + ConfigurationWriter<Configuration = <A as ConfigurationReader>::Configuration> + Send + 'static,
+              F: FnOnce(&mut <A as ConfigurationReader>::Configuration) + Send + 'static
+    {
+        let accessor = self.accessor.clone();
+
+        self.writes
+            .turn()
+            .then(move |turn| {
+                let turn = match turn {
+                    Ok(turn) => turn,
+                    Err(never) => never,
+                };
+
+                let read = lock(&accessor).read_configuration();
+                read.map_err(LensWriteError::ReadError)
+                    .and_then(move |mut configuration| {
+                        modify(&mut configuration);
+
+                        let write = lock(&accessor).write_configuration(&configuration);
+                        write.map_err(LensWriteError::WriteError)
+                    })
+                    .then(move |result| {
+                        // The next write takes its turn once this one has completed
+                        drop(turn);
+                        result
+                    })
+            })
+            .boxed()
+    }
+}
+
+impl<A> Clone for LensParent<A> {
+    fn clone(&self) -> Self {
+        // Clone should share the same accessor and writes
+        Self {
+            accessor: self.accessor.clone(),
+            writes: self.writes.clone(),
+        }
+    }
+}
+
+impl<A> ConfigurationReader for LensParent<A>
+    where A: ConfigurationReader
+{
+    type Configuration = A::Configuration;
+    type Error = A::Error;
+    type ReadResult = A::ReadResult;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        lock(&self.accessor).read_configuration()
+    }
+}
+
+impl<A> ConfigurationWriter for LensParent<A>
+    where A: ConfigurationWriter + Send + 'static,
+          A::Configuration: Clone
+{
+    type Configuration = A::Configuration;
+    type Error = A::Error;
+    type WriteResult = BoxFuture<(), Self::Error>;
+
+    fn write_configuration(&mut self, configuration: &Self::Configuration) -> Self::WriteResult {
+        let accessor = self.accessor.clone();
+        let configuration = configuration.clone();
+
+        self.writes
+            .turn()
+            .then(move |turn| {
+                let turn = match turn {
+                    Ok(turn) => turn,
+                    Err(never) => never,
+                };
+
+                let write = lock(&accessor).write_configuration(&configuration);
+                write.then(move |result| {
+                    drop(turn);
+                    result
+                })
+            })
+            .boxed()
+    }
+}
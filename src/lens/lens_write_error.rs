@@ -0,0 +1,35 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The error of writing a section through a `LensAccessor`, either reading or writing the parent configuration
+/// failed.
+#[derive(Debug, PartialEq, Eq)]
+pub enum LensWriteError<R, W> {
+    ReadError(R),
+    WriteError(W),
+}
+
+impl<R: Display, W: Display> Display for LensWriteError<R, W> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            LensWriteError::ReadError(ref err) => write!(f, "Read Error {}", err),
+            LensWriteError::WriteError(ref err) => write!(f, "Write Error {}", err),
+        }
+    }
+}
+
+impl<R: Error, W: Error> Error for LensWriteError<R, W> {
+    fn description(&self) -> &str {
+        match *self {
+            LensWriteError::ReadError(ref err) => err.description(),
+            LensWriteError::WriteError(ref err) => err.description(),
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            LensWriteError::ReadError(ref err) => Some(err),
+            LensWriteError::WriteError(ref err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+mod lens_write_error;
+pub use self::lens_write_error::*;
+
+mod write_queue;
+
+mod lens_parent;
+pub use self::lens_parent::*;
+
+mod lens_accessor;
+pub use self::lens_accessor::*;
@@ -0,0 +1,119 @@
+use futures::{Async, Future, Poll};
+use futures::sync::oneshot;
+use std::collections::VecDeque;
+use std::mem;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+#[derive(Debug, Default)]
+struct Queue {
+    writing: bool,
+    waiting: VecDeque<oneshot::Sender<()>>,
+}
+
+fn lock(queue: &Mutex<Queue>) -> MutexGuard<Queue> {
+    // Nothing panics while the lock is held, should it happen the queue is still usable
+    queue.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Passes the turn to the next write which is still waiting, or lets the next write through at once.
+fn pass_turn(queue: &Mutex<Queue>) {
+    let mut queue = lock(queue);
+
+    while let Some(waiting) = queue.waiting.pop_front() {
+        if waiting.send(()).is_ok() {
+            return;
+        }
+    }
+
+    queue.writing = false;
+}
+
+/// Lets one write through at a time, in the order they asked for their turn, without blocking the thread of
+/// the writes which are waiting.
+#[derive(Debug, Default, Clone)]
+pub(super) struct WriteQueue {
+    queue: Arc<Mutex<Queue>>,
+}
+
+impl WriteQueue {
+    pub(super) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Waits for the turn of a write, the turn is only asked for once the returned future is polled.
+    pub(super) fn turn(&self) -> WaitForTurn {
+        WaitForTurn {
+            queue: self.queue.clone(),
+            state: WaitState::Asking,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum WaitState {
+    Asking,
+    Waiting(oneshot::Receiver<()>),
+    Done,
+}
+
+/// A future which completes with the `WriteTurn` of a write once the writes before it have finished.
+#[derive(Debug)]
+pub(super) struct WaitForTurn {
+    queue: Arc<Mutex<Queue>>,
+    state: WaitState,
+}
+
+impl Future for WaitForTurn {
+    type Item = WriteTurn;
+    type Error = !;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if let WaitState::Asking = self.state {
+            let mut queue = lock(&self.queue);
+
+            if queue.writing {
+                let (turn, waiting) = oneshot::channel();
+                queue.waiting.push_back(turn);
+                self.state = WaitState::Waiting(waiting);
+            } else {
+                queue.writing = true;
+                self.state = WaitState::Done;
+            }
+        }
+
+        if let WaitState::Waiting(ref mut waiting) = self.state {
+            // The queue never drops a waiting write without passing it the turn
+            match waiting.poll().expect("the write queue dropped a waiting write") {
+                Async::Ready(()) => {}
+                Async::NotReady => return Ok(Async::NotReady),
+            }
+        }
+
+        self.state = WaitState::Done;
+        Ok(Async::Ready(WriteTurn { queue: self.queue.clone() }))
+    }
+}
+
+impl Drop for WaitForTurn {
+    fn drop(&mut self) {
+        // A write given its turn after it stopped being polled passes the turn on
+        if let WaitState::Waiting(mut waiting) = mem::replace(&mut self.state, WaitState::Done) {
+            waiting.close();
+            if let Ok(Some(())) = waiting.try_recv() {
+                pass_turn(&self.queue);
+            }
+        }
+    }
+}
+
+/// The turn of a write in a `WriteQueue`, the next write is let through once it is dropped.
+#[derive(Debug)]
+pub(super) struct WriteTurn {
+    queue: Arc<Mutex<Queue>>,
+}
+
+impl Drop for WriteTurn {
+    fn drop(&mut self) {
+        pass_turn(&self.queue);
+    }
+}
@@ -4,6 +4,22 @@ extern crate futures;
 #[macro_use]
 extern crate quick_error;
 extern crate either;
+extern crate serde;
+#[cfg(feature = "derive")]
+extern crate lz_configuration_derive;
+#[cfg(feature = "json")]
+extern crate serde_json;
+#[cfg(feature = "toml")]
+extern crate toml_edit;
+#[cfg(feature = "yaml")]
+extern crate serde_yaml;
+
+#[cfg(test)]
+#[macro_use]
+extern crate serde_derive;
+
+#[cfg(test)]
+extern crate tempdir;
 
 mod configuration_reader;
 pub use self::configuration_reader::*;
@@ -14,9 +30,18 @@ pub use self::configuration_writer::*;
 mod configuration_accessor;
 pub use self::configuration_accessor::*;
 
+mod box_configuration_reader;
+pub use self::box_configuration_reader::*;
+
+mod box_configuration_writer;
+pub use self::box_configuration_writer::*;
+
 mod configuration_target;
 pub use self::configuration_target::*;
 
+mod configuration_codec;
+pub use self::configuration_codec::*;
+
 mod copy_configuration_error;
 pub use self::copy_configuration_error::*;
 
@@ -48,6 +73,22 @@ pub mod fallback;
 pub mod cache;
 pub mod closure;
 pub mod copy_on_read;
+pub mod file;
+pub mod value;
+pub mod env;
+pub mod args;
+pub mod codec;
+pub mod http;
+pub mod directory;
+pub mod merge;
+pub mod map;
+pub mod lens;
+#[macro_use]
+pub mod zip;
+pub mod snapshot;
 
 mod fluent_configuration_reader;
-pub use self::fluent_configuration_reader::*;
\ No newline at end of file
+pub use self::fluent_configuration_reader::*;
+
+mod fluent_configuration_writer;
+pub use self::fluent_configuration_writer::*;
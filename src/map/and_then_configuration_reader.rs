@@ -0,0 +1,91 @@
+use ConfigurationReader;
+use futures::{BoxFuture, Future};
+use std::fmt::Debug;
+use std::sync::Arc;
+use either::Either;
+
+/// A `ConfigurationReader` which transforms the configuration read by another reader with a transform
+/// which may fail.
+///
+/// The error is `Either::Left` when the reader fails and `Either::Right` when the transform fails.
+#[derive(Debug)]
+pub struct AndThenConfigurationReader<R, F> {
+    reader: R,
+    and_then: Arc<F>,
+}
+
+impl<R, F, U, E> AndThenConfigurationReader<R, F>
+    where R: ConfigurationReader,
+          F: Fn(R::Configuration) -> Result<U, E>
+{
+    pub fn new(reader: R, and_then: F) -> Self {
+        Self {
+            reader: reader,
+            and_then: Arc::new(and_then),
+        }
+    }
+}
+
+impl<R: Clone, F> Clone for AndThenConfigurationReader<R, F> {
+    fn clone(&self) -> Self {
+        Self {
+            reader: self.reader.clone(),
+            and_then: self.and_then.clone(),
+        }
+    }
+}
+
+impl<R, F, U, E> ConfigurationReader for AndThenConfigurationReader<R, F>
+    where R: ConfigurationReader,
+          F: Fn(R::Configuration) -> Result<U, E> + Send + Sync + 'static,
+          U: Send + 'static,
+          E: Debug + Send + 'static
+{
+    type Configuration = U;
+    type Error = Either<R::Error, E>;
+    type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        let and_then = self.and_then.clone();
+
+        self.reader
+            .read_configuration()
+            .map_err(Either::Left)
+            .and_then(move |configuration| and_then(configuration).map_err(Either::Right))
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationReader, FluentConfigurationReader};
+    use memory::MemoryConfigurationAccessor;
+    use futures::Future;
+    use either::Either;
+    use std::num::ParseIntError;
+
+    #[test]
+    fn read_configuration_returns_transformed_configuration() {
+        // Arrange
+        let reader = MemoryConfigurationAccessor::new("8080".to_owned()).and_then(|port: String| port.parse::<u16>());
+
+        // Act
+        let configuration = reader.read_configuration().wait();
+
+        // Assert
+        assert_eq!(configuration, Ok(8080));
+    }
+
+    #[test]
+    fn read_configuration_failed_transform_returns_error() {
+        // Arrange
+        let reader = MemoryConfigurationAccessor::new("http".to_owned()).and_then(|port: String| port.parse::<u16>());
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        let expected: ParseIntError = "http".parse::<u16>().unwrap_err();
+        assert_eq!(error, Either::Right(expected));
+    }
+}
@@ -0,0 +1,59 @@
+use ConfigurationWriter;
+use std::marker::PhantomData;
+
+/// A `ConfigurationWriter` which transforms a configuration before writing it with another writer.
+#[derive(Debug)]
+pub struct ContramapConfigurationWriter<W, F, C> {
+    writer: W,
+    contramap: F,
+    phantom_data: PhantomData<C>,
+}
+
+impl<W, F, C> ContramapConfigurationWriter<W, F, C>
+    where W: ConfigurationWriter,
+          F: Fn(&C) -> W::Configuration
+{
+    pub fn new(writer: W, contramap: F) -> Self {
+        Self {
+            writer: writer,
+            contramap: contramap,
+            phantom_data: Default::default(),
+        }
+    }
+}
+
+impl<W, F, C> ConfigurationWriter for ContramapConfigurationWriter<W, F, C>
+    where W: ConfigurationWriter,
+          F: Fn(&C) -> W::Configuration,
+          C: Send + 'static
+{
+    type Configuration = C;
+    type Error = W::Error;
+    type WriteResult = W::WriteResult;
+
+    fn write_configuration(&mut self, configuration: &Self::Configuration) -> Self::WriteResult {
+        let configuration = (self.contramap)(configuration);
+
+        self.writer.write_configuration(&configuration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationReader, ConfigurationWriter, FluentConfigurationWriter};
+    use memory::MemoryConfigurationAccessor;
+    use futures::Future;
+
+    #[test]
+    fn write_configuration_writes_transformed_configuration() {
+        // Arrange
+        let memory = MemoryConfigurationAccessor::empty();
+        let mut writer = memory.clone().contramap(|port: &u16| port.to_string());
+
+        // Act
+        writer.write_configuration(&8080).wait().unwrap();
+
+        // Assert
+        assert_eq!(memory.read_configuration().wait(), Ok("8080".to_owned()));
+    }
+}
@@ -0,0 +1,35 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The error of reading through a `FilterMapConfigurationReader`, either the reader failed or the configuration
+/// it read was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterMapConfigurationReadError<E> {
+    ReadError(E),
+    Rejected,
+}
+
+impl<E: Display> Display for FilterMapConfigurationReadError<E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            FilterMapConfigurationReadError::ReadError(ref err) => write!(f, "Read Error {}", err),
+            FilterMapConfigurationReadError::Rejected => write!(f, "Rejected"),
+        }
+    }
+}
+
+impl<E: Error> Error for FilterMapConfigurationReadError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            FilterMapConfigurationReadError::ReadError(ref err) => err.description(),
+            FilterMapConfigurationReadError::Rejected => "the configuration was rejected",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            FilterMapConfigurationReadError::ReadError(ref err) => Some(err),
+            FilterMapConfigurationReadError::Rejected => None,
+        }
+    }
+}
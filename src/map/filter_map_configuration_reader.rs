@@ -0,0 +1,97 @@
+use ConfigurationReader;
+use super::FilterMapConfigurationReadError;
+use futures::{BoxFuture, Future};
+use std::sync::Arc;
+
+/// A `ConfigurationReader` which transforms the configuration read by another reader, rejecting the
+/// configuration when the transform returns `None`.
+#[derive(Debug)]
+pub struct FilterMapConfigurationReader<R, F> {
+    reader: R,
+    filter_map: Arc<F>,
+}
+
+impl<R, F, U> FilterMapConfigurationReader<R, F>
+    where R: ConfigurationReader,
+          F: Fn(R::Configuration) -> Option<U>
+{
+    pub fn new(reader: R, filter_map: F) -> Self {
+        Self {
+            reader: reader,
+            filter_map: Arc::new(filter_map),
+        }
+    }
+}
+
+impl<R: Clone, F> Clone for FilterMapConfigurationReader<R, F> {
+    fn clone(&self) -> Self {
+        Self {
+            reader: self.reader.clone(),
+            filter_map: self.filter_map.clone(),
+        }
+    }
+}
+
+impl<R, F, U> ConfigurationReader for FilterMapConfigurationReader<R, F>
+    where R: ConfigurationReader,
+          F: Fn(R::Configuration) -> Option<U> + Send + Sync + 'static,
+          U: Send + 'static
+{
+    type Configuration = U;
+    type Error = FilterMapConfigurationReadError<R::Error>;
+    type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        let filter_map = self.filter_map.clone();
+
+        self.reader
+            .read_configuration()
+            .map_err(FilterMapConfigurationReadError::ReadError)
+            .and_then(move |configuration| filter_map(configuration).ok_or(FilterMapConfigurationReadError::Rejected))
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationReader, FluentConfigurationReader};
+    use map::FilterMapConfigurationReadError;
+    use memory::{MemoryConfigurationAccessor, MemoryConfigurationReadError};
+    use futures::Future;
+
+    #[test]
+    fn read_configuration_returns_transformed_configuration() {
+        // Arrange
+        let reader = MemoryConfigurationAccessor::new("8080".to_owned()).filter_map(|port: String| port.parse::<u16>().ok());
+
+        // Act
+        let configuration = reader.read_configuration().wait();
+
+        // Assert
+        assert_eq!(configuration, Ok(8080));
+    }
+
+    #[test]
+    fn read_configuration_rejected_returns_error() {
+        // Arrange
+        let reader = MemoryConfigurationAccessor::new(0u16).filter_map(|port| if port == 0 { None } else { Some(port) });
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        assert_eq!(error, FilterMapConfigurationReadError::Rejected);
+    }
+
+    #[test]
+    fn read_configuration_failed_read_returns_error() {
+        // Arrange
+        let reader = MemoryConfigurationAccessor::<u16>::empty().filter_map(Some);
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        assert_eq!(error, FilterMapConfigurationReadError::ReadError(MemoryConfigurationReadError::NoConfiguration));
+    }
+}
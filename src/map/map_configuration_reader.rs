@@ -0,0 +1,69 @@
+use ConfigurationReader;
+use futures::{BoxFuture, Future};
+use std::sync::Arc;
+
+/// A `ConfigurationReader` which transforms the configuration read by another reader.
+#[derive(Debug)]
+pub struct MapConfigurationReader<R, F> {
+    reader: R,
+    map: Arc<F>,
+}
+
+impl<R, F, U> MapConfigurationReader<R, F>
+    where R: ConfigurationReader,
+          F: Fn(R::Configuration) -> U
+{
+    pub fn new(reader: R, map: F) -> Self {
+        Self {
+            reader: reader,
+            map: Arc::new(map),
+        }
+    }
+}
+
+impl<R: Clone, F> Clone for MapConfigurationReader<R, F> {
+    fn clone(&self) -> Self {
+        Self {
+            reader: self.reader.clone(),
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<R, F, U> ConfigurationReader for MapConfigurationReader<R, F>
+    where R: ConfigurationReader,
+          F: Fn(R::Configuration) -> U + Send + Sync + 'static,
+          U: Send + 'static
+{
+    type Configuration = U;
+    type Error = R::Error;
+    type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        let map = self.map.clone();
+
+        self.reader
+            .read_configuration()
+            .map(move |configuration| map(configuration))
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationReader, FluentConfigurationReader};
+    use memory::MemoryConfigurationAccessor;
+    use futures::Future;
+
+    #[test]
+    fn read_configuration_returns_mapped_configuration() {
+        // Arrange
+        let reader = MemoryConfigurationAccessor::new(21).map(|value: u32| value * 2);
+
+        // Act
+        let configuration = reader.read_configuration().wait();
+
+        // Assert
+        assert_eq!(configuration, Ok(42));
+    }
+}
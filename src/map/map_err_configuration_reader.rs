@@ -0,0 +1,71 @@
+use ConfigurationReader;
+use futures::{BoxFuture, Future};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// A `ConfigurationReader` which transforms the error of another reader.
+#[derive(Debug)]
+pub struct MapErrConfigurationReader<R, F> {
+    reader: R,
+    map_err: Arc<F>,
+}
+
+impl<R, F, E> MapErrConfigurationReader<R, F>
+    where R: ConfigurationReader,
+          F: Fn(R::Error) -> E
+{
+    pub fn new(reader: R, map_err: F) -> Self {
+        Self {
+            reader: reader,
+            map_err: Arc::new(map_err),
+        }
+    }
+}
+
+impl<R: Clone, F> Clone for MapErrConfigurationReader<R, F> {
+    fn clone(&self) -> Self {
+        Self {
+            reader: self.reader.clone(),
+            map_err: self.map_err.clone(),
+        }
+    }
+}
+
+impl<R, F, E> ConfigurationReader for MapErrConfigurationReader<R, F>
+    where R: ConfigurationReader,
+          F: Fn(R::Error) -> E + Send + Sync + 'static,
+          E: Debug + Send + 'static
+{
+    type Configuration = R::Configuration;
+    type Error = E;
+    type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        let map_err = self.map_err.clone();
+
+        self.reader
+            .read_configuration()
+            .map_err(move |e| map_err(e))
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationReader, FluentConfigurationReader};
+    use memory::{MemoryConfigurationAccessor, MemoryConfigurationReadError};
+    use futures::Future;
+
+    #[test]
+    fn read_configuration_returns_mapped_error() {
+        // Arrange
+        let reader = MemoryConfigurationAccessor::<u32>::empty()
+            .map_err(|e: MemoryConfigurationReadError| format!("settings unavailable: {:?}", e));
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        assert_eq!(error, "settings unavailable: NoConfiguration");
+    }
+}
@@ -0,0 +1,17 @@
+mod map_configuration_reader;
+pub use self::map_configuration_reader::*;
+
+mod and_then_configuration_reader;
+pub use self::and_then_configuration_reader::*;
+
+mod filter_map_configuration_read_error;
+pub use self::filter_map_configuration_read_error::*;
+
+mod filter_map_configuration_reader;
+pub use self::filter_map_configuration_reader::*;
+
+mod map_err_configuration_reader;
+pub use self::map_err_configuration_reader::*;
+
+mod contramap_configuration_writer;
+pub use self::contramap_configuration_writer::*;
@@ -23,6 +23,15 @@ impl<C> MemoryConfigurationAccessor<C>{
             configuration: Arc::new(RwLock::new(configuration.into()))
         }
     }
+
+    /// Clears the configuration of this accessor and its clones.
+    pub fn clear(&self){
+        *self.configuration.write().unwrap() = None;
+    }
+
+    pub fn is_empty(&self) -> bool{
+        self.configuration.read().unwrap().is_none()
+    }
 }
 
 impl<C> From<MemoryConfigurationAccessor<C>> for ConfigurationAccessor<MemoryConfigurationAccessor<C>, MemoryConfigurationAccessor<C>>
@@ -139,4 +148,18 @@ mod tests {
         assert_eq!(first_configuration, TestConfiguration(5));
         assert_eq!(second_configuration, TestConfiguration(5));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn clear_clears_cloned_accessors() {
+        // Arrange
+        let accessor = MemoryConfigurationAccessor::new(TestConfiguration(5));
+        let cloned_accessor = accessor.clone();
+
+        // Act
+        accessor.clear();
+
+        // Assert
+        assert!(cloned_accessor.is_empty());
+        assert_eq!(cloned_accessor.read_configuration().wait().unwrap_err(), MemoryConfigurationReadError::NoConfiguration);
+    }
+}
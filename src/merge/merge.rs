@@ -0,0 +1,116 @@
+use value::ConfigurationValue;
+use std::collections::{BTreeMap, HashMap};
+use std::collections::hash_map::Entry as HashMapEntry;
+use std::collections::btree_map::Entry as BTreeMapEntry;
+use std::hash::{BuildHasher, Hash};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// A configuration which can have another configuration of the same type merged over it.
+///
+/// Nested structures such as maps are merged deeply, so only the values which are present in `other`
+/// replace those in `self`. Plain values and sequences are replaced as a whole.
+pub trait Merge {
+    fn merge(&mut self, other: Self);
+}
+
+macro_rules! merge_by_replacing {
+    ($($t:ty),*) => {
+        $(
+            impl Merge for $t {
+                fn merge(&mut self, other: Self) {
+                    *self = other;
+                }
+            }
+        )*
+    };
+}
+
+merge_by_replacing!(bool, char, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64, String, PathBuf, Duration);
+
+impl<T> Merge for Vec<T> {
+    fn merge(&mut self, other: Self) {
+        *self = other;
+    }
+}
+
+/// A missing value in `other` keeps the value in `self`.
+impl<T: Merge> Merge for Option<T> {
+    fn merge(&mut self, other: Self) {
+        if let Some(other) = other {
+            match *self {
+                Some(ref mut value) => value.merge(other),
+                None => *self = Some(other),
+            }
+        }
+    }
+}
+
+impl<K: Ord, V: Merge> Merge for BTreeMap<K, V> {
+    fn merge(&mut self, other: Self) {
+        for (key, value) in other {
+            match self.entry(key) {
+                BTreeMapEntry::Occupied(mut entry) => entry.get_mut().merge(value),
+                BTreeMapEntry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+            }
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Merge, S: BuildHasher> Merge for HashMap<K, V, S> {
+    fn merge(&mut self, other: Self) {
+        for (key, value) in other {
+            match self.entry(key) {
+                HashMapEntry::Occupied(mut entry) => entry.get_mut().merge(value),
+                HashMapEntry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+            }
+        }
+    }
+}
+
+impl Merge for ConfigurationValue {
+    fn merge(&mut self, other: Self) {
+        ConfigurationValue::merge(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use merge::Merge;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn merge_option_keeps_missing_values() {
+        // Arrange
+        let mut value = Some(8080);
+
+        // Act
+        value.merge(None);
+
+        // Assert
+        assert_eq!(value, Some(8080));
+    }
+
+    #[test]
+    fn merge_map_merges_nested_values() {
+        // Arrange
+        let mut value = BTreeMap::new();
+        value.insert("server", Some(vec!["a"]));
+        value.insert("client", Some(vec!["b"]));
+
+        let mut other = BTreeMap::new();
+        other.insert("server", None);
+        other.insert("client", Some(vec!["c"]));
+
+        // Act
+        Merge::merge(&mut value, other);
+
+        // Assert
+        assert_eq!(value.get("server"), Some(&Some(vec!["a"])));
+        assert_eq!(value.get("client"), Some(&Some(vec!["c"])));
+    }
+}
@@ -0,0 +1,194 @@
+use ConfigurationReader;
+use super::Merge;
+use futures::{BoxFuture, Future};
+use either::Either;
+
+/// A `ConfigurationReader` which reads a base configuration and an overlay configuration and merges the
+/// overlay over the base.
+///
+/// Layers can be stacked in priority order with `push_layer`, for example defaults, then a system file,
+/// then a user file and then the environment, each layer only overriding the values it contains.
+#[derive(Debug, Clone)]
+pub struct MergeConfigurationReader<B, O, P> {
+    base: B,
+    overlay: O,
+    should_skip: P,
+}
+
+fn predicate_false<T>(_: &T) -> bool {
+    false
+}
+
+impl<B, O> MergeConfigurationReader<B, O, fn(&O::Error) -> bool>
+    where B: ConfigurationReader,
+          O: ConfigurationReader<Configuration = B::Configuration>
+{
+    /// Creates a new `MergeConfigurationReader<B, O, P>` which fails when either reader fails.
+    pub fn new(base: B, overlay: O) -> Self {
+        Self::new_conditional(base, overlay, predicate_false as fn(&O::Error) -> bool)
+    }
+}
+
+impl<B, O, P> MergeConfigurationReader<B, O, P>
+    where B: ConfigurationReader,
+          O: ConfigurationReader<Configuration = B::Configuration>,
+          P: Fn(&O::Error) -> bool
+{
+    /// Creates a new `MergeConfigurationReader<B, O, P>` which returns the base configuration alone when
+    /// the overlay fails with an error that `should_skip` returns `true` for, such as a missing file.
+    pub fn new_conditional(base: B, overlay: O, should_skip: P) -> Self {
+        Self {
+            base: base,
+            overlay: overlay,
+            should_skip: should_skip,
+        }
+    }
+}
+
+impl<B, O, P> MergeConfigurationReader<B, O, P> {
+    /// Merges the configuration of another reader over the configuration of this reader.
+    pub fn push_layer<L>(self, layer: L) -> MergeConfigurationReader<Self, L, fn(&L::Error) -> bool>
+        where Self: ConfigurationReader,
+              L: ConfigurationReader<Configuration = <Self as ConfigurationReader>::Configuration>
+    {
+        MergeConfigurationReader::new(self, layer)
+    }
+
+    /// Merges the configuration of another reader over the configuration of this reader, skipping the
+    /// layer when it fails with an error that `should_skip` returns `true` for.
+    pub fn push_conditional_layer<L, LP>(self, layer: L, should_skip: LP) -> MergeConfigurationReader<Self, L, LP>
+        where Self: ConfigurationReader,
+              L: ConfigurationReader<Configuration = <Self as ConfigurationReader>::Configuration>,
+              LP: Fn(&L::Error) -> bool
+    {
+        MergeConfigurationReader::new_conditional(self, layer, should_skip)
+    }
+}
+
+impl<B, O, P> ConfigurationReader for MergeConfigurationReader<B, O, P>
+    where B: ConfigurationReader,
+          B::Configuration: Merge + Send,
+          O: ConfigurationReader<Configuration = B::Configuration>,
+          P: Fn(&O::Error) -> bool + Send + Copy + 'static
+{
+    type Configuration = B::Configuration;
+    type Error = Either<B::Error, O::Error>;
+    type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        let should_skip = self.should_skip;
+
+        let overlay = self.overlay
+            .read_configuration()
+            .map(Some)
+            .or_else(move |e| if (should_skip)(&e) { Ok(None) } else { Err(e) })
+            .map_err(Either::Right);
+
+        self.base
+            .read_configuration()
+            .map_err(Either::Left)
+            .join(overlay)
+            .map(|(mut configuration, overlay)| {
+                if let Some(overlay) = overlay {
+                    configuration.merge(overlay);
+                }
+
+                configuration
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ConfigurationReader;
+    use memory::{MemoryConfigurationAccessor, MemoryConfigurationReadError};
+    use merge::{Merge, MergeConfigurationReader};
+    use value::ConfigurationValue;
+    use futures::Future;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct TestConfiguration {
+        host: Option<String>,
+        port: Option<u16>,
+    }
+
+    impl Merge for TestConfiguration {
+        fn merge(&mut self, other: Self) {
+            self.host.merge(other.host);
+            self.port.merge(other.port);
+        }
+    }
+
+    fn layer(host: Option<&str>, port: Option<u16>) -> MemoryConfigurationAccessor<TestConfiguration> {
+        MemoryConfigurationAccessor::new(TestConfiguration {
+            host: host.map(str::to_owned),
+            port: port,
+        })
+    }
+
+    #[test]
+    fn read_configuration_merges_layers_in_order() {
+        // Arrange
+        let reader = MergeConfigurationReader::new(layer(Some("localhost"), Some(8080)), layer(None, Some(9090)))
+            .push_layer(layer(Some("example.com"), None));
+
+        // Act
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration,
+                   TestConfiguration {
+                       host: Some("example.com".to_owned()),
+                       port: Some(9090),
+                   });
+    }
+
+    #[test]
+    fn read_configuration_merges_nested_values() {
+        // Arrange
+        let mut defaults = ConfigurationValue::table();
+        defaults.insert(&["server", "host"], "localhost".into());
+        defaults.insert(&["server", "port"], "8080".into());
+
+        let mut user = ConfigurationValue::table();
+        user.insert(&["server", "port"], "9090".into());
+
+        let reader = MergeConfigurationReader::new(MemoryConfigurationAccessor::new(defaults),
+                                                   MemoryConfigurationAccessor::new(user));
+
+        // Act
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration.get(&["server", "host"]), Some(&ConfigurationValue::from("localhost")));
+        assert_eq!(configuration.get(&["server", "port"]), Some(&ConfigurationValue::from("9090")));
+    }
+
+    #[test]
+    fn read_configuration_skips_failed_conditional_layer() {
+        // Arrange
+        let reader = MergeConfigurationReader::new(layer(Some("localhost"), Some(8080)), layer(None, Some(9090)))
+            .push_conditional_layer(MemoryConfigurationAccessor::empty(),
+                                    |e: &MemoryConfigurationReadError| *e == MemoryConfigurationReadError::NoConfiguration);
+
+        // Act
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration.port, Some(9090));
+    }
+
+    #[test]
+    fn read_configuration_failed_layer_returns_error() {
+        // Arrange
+        let reader = MergeConfigurationReader::new(layer(Some("localhost"), Some(8080)),
+                                                   MemoryConfigurationAccessor::empty());
+
+        // Act
+        let result = reader.read_configuration().wait();
+
+        // Assert
+        assert!(result.unwrap_err().is_right());
+    }
+}
@@ -0,0 +1,37 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The error when a partial configuration is finalized while required fields are still missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingFieldsError {
+    fields: Vec<String>,
+}
+
+impl MissingFieldsError {
+    /// Creates a new `MissingFieldsError` for the specified fields, nested fields are joined with `.`.
+    pub fn new(fields: Vec<String>) -> Self {
+        Self { fields: fields }
+    }
+
+    /// Gets the names of every missing field.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// Gets the names of every missing field prefixed with the name of the field containing them.
+    pub fn within(&self, parent: &str) -> Vec<String> {
+        self.fields.iter().map(|field| format!("{}.{}", parent, field)).collect()
+    }
+}
+
+impl Display for MissingFieldsError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "missing fields '{}'", self.fields.join("', '"))
+    }
+}
+
+impl Error for MissingFieldsError {
+    fn description(&self) -> &str {
+        "missing fields"
+    }
+}
@@ -0,0 +1,14 @@
+mod merge;
+pub use self::merge::*;
+
+mod missing_fields_error;
+pub use self::missing_fields_error::*;
+
+mod partial_configuration;
+pub use self::partial_configuration::*;
+
+mod merge_configuration_reader;
+pub use self::merge_configuration_reader::*;
+
+#[cfg(feature = "derive")]
+pub use lz_configuration_derive::{Merge, PartialConfiguration};
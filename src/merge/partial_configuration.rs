@@ -0,0 +1,13 @@
+use super::{Merge, MissingFieldsError};
+
+/// A mirror of a configuration where every field is optional, so each layer of a configuration only
+/// needs to supply some of the fields.
+///
+/// Layers are combined with `Merge` and the result is converted into the complete configuration with
+/// `finalize`. This is usually implemented with `#[derive(PartialConfiguration)]` on the configuration.
+pub trait PartialConfiguration: Merge + Default {
+    type Configuration;
+
+    /// Converts this into the complete configuration, failing with every required field which is missing.
+    fn finalize(self) -> Result<Self::Configuration, MissingFieldsError>;
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+/// A configuration read by a `SnapshotConfigurationReader`, either fresh from its reader or stale from its
+/// snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastKnownGood<C> {
+    configuration: C,
+    stale_age: Option<Duration>,
+}
+
+impl<C> LastKnownGood<C> {
+    /// Creates a new `LastKnownGood<C>` for a configuration read from the reader.
+    pub fn fresh(configuration: C) -> Self {
+        Self {
+            configuration: configuration,
+            stale_age: None,
+        }
+    }
+
+    /// Creates a new `LastKnownGood<C>` for a configuration read from a snapshot of the specified age.
+    pub fn stale(configuration: C, age: Duration) -> Self {
+        Self {
+            configuration: configuration,
+            stale_age: Some(age),
+        }
+    }
+
+    pub fn configuration(&self) -> &C {
+        &self.configuration
+    }
+
+    pub fn into_configuration(self) -> C {
+        self.configuration
+    }
+
+    /// Gets whether the configuration was read from the snapshot because the reader failed.
+    pub fn is_stale(&self) -> bool {
+        self.stale_age.is_some()
+    }
+
+    /// Gets how long ago the configuration of the snapshot was last read when the configuration is stale.
+    pub fn stale_age(&self) -> Option<Duration> {
+        self.stale_age
+    }
+}
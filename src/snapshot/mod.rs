@@ -0,0 +1,8 @@
+mod last_known_good;
+pub use self::last_known_good::*;
+
+mod snapshot_configuration_read_error;
+pub use self::snapshot_configuration_read_error::*;
+
+mod snapshot_configuration_reader;
+pub use self::snapshot_configuration_reader::*;
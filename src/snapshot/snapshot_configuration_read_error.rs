@@ -0,0 +1,46 @@
+use file::FileConfigurationReadError;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The error of a `SnapshotConfigurationReader` when both its reader and its snapshot failed.
+#[derive(Debug)]
+pub struct SnapshotConfigurationReadError<R, E> {
+    read_error: R,
+    snapshot_error: FileConfigurationReadError<E>,
+}
+
+impl<R, E> SnapshotConfigurationReadError<R, E> {
+    pub fn new(read_error: R, snapshot_error: FileConfigurationReadError<E>) -> Self {
+        Self {
+            read_error: read_error,
+            snapshot_error: snapshot_error,
+        }
+    }
+
+    pub fn read_error(&self) -> &R {
+        &self.read_error
+    }
+
+    pub fn snapshot_error(&self) -> &FileConfigurationReadError<E> {
+        &self.snapshot_error
+    }
+}
+
+impl<R: Display, E: Display> Display for SnapshotConfigurationReadError<R, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f,
+               "Read: {}\nSnapshot: {}",
+               self.read_error,
+               self.snapshot_error)
+    }
+}
+
+impl<R: Error, E: Error> Error for SnapshotConfigurationReadError<R, E> {
+    fn description(&self) -> &str {
+        self.read_error.description()
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        Some(&self.read_error)
+    }
+}
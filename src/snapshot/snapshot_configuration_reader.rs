@@ -0,0 +1,313 @@
+use {ConfigurationCodec, ConfigurationReader, ConfigurationWriter};
+use super::{LastKnownGood, SnapshotConfigurationReadError};
+use file::{FileConfigurationAccessor, FileConfigurationWriteError};
+use futures::{BoxFuture, Future};
+use futures::future::ok;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug)]
+struct SnapshotSave<E> {
+    // The configuration last saved as encoded, so an unchanged configuration is not saved again
+    saved: Option<Vec<u8>>,
+    error: Option<FileConfigurationWriteError<E>>,
+}
+
+/// A `ConfigurationReader` which saves every configuration read by another reader to a snapshot file, and
+/// reads the last known good configuration from the snapshot when the reader fails.
+///
+/// Unlike `with_cache` the reader is always read first, the snapshot is only used when the reader fails and
+/// the configuration it returns is flagged as stale with the age of the snapshot.
+///
+/// The snapshot is only saved when the configuration changed or the snapshot was removed, one save at a time.
+/// The time of every read is recorded next to the snapshot in a `.read` file, the age of a stale configuration
+/// is how long ago it was last read. Failing to save the snapshot does not fail the read, the error is kept
+/// until it is taken with `take_save_error`.
+#[derive(Debug)]
+pub struct SnapshotConfigurationReader<R: ConfigurationReader, Codec: ConfigurationCodec<R::Configuration>> {
+    reader: R,
+    snapshot: FileConfigurationAccessor<R::Configuration, Codec>,
+    save: Arc<Mutex<SnapshotSave<Codec::Error>>>,
+}
+
+impl<R, Codec> SnapshotConfigurationReader<R, Codec>
+    where R: ConfigurationReader,
+          Codec: ConfigurationCodec<R::Configuration>
+{
+    /// Creates a new `SnapshotConfigurationReader<R, Codec>` which saves the snapshot at the specified path.
+    pub fn new<P: Into<PathBuf>>(reader: R, path: P, codec: Codec) -> Self {
+        Self {
+            reader: reader,
+            snapshot: FileConfigurationAccessor::new(path, codec),
+            save: Arc::new(Mutex::new(SnapshotSave {
+                saved: None,
+                error: None,
+            })),
+        }
+    }
+
+    pub fn reader(&self) -> &R {
+        &self.reader
+    }
+
+    /// Gets the path of the snapshot file.
+    pub fn path(&self) -> &Path {
+        self.snapshot.path()
+    }
+
+    /// Takes the error of the last save of the snapshot when it failed, no error is kept once a save succeeds.
+    pub fn take_save_error(&self) -> Option<FileConfigurationWriteError<Codec::Error>> {
+        self.save.lock().unwrap().error.take()
+    }
+}
+
+impl<R, Codec> Clone for SnapshotConfigurationReader<R, Codec>
+    where R: ConfigurationReader + Clone,
+          Codec: ConfigurationCodec<R::Configuration> + Clone
+{
+    fn clone(&self) -> Self {
+        // Clone should share the same saves
+        Self {
+            reader: self.reader.clone(),
+            snapshot: self.snapshot.clone(),
+            save: self.save.clone(),
+        }
+    }
+}
+
+fn save_snapshot<C, Codec>(save: &Mutex<SnapshotSave<Codec::Error>>,
+                           snapshot: &mut FileConfigurationAccessor<C, Codec>,
+                           configuration: &C)
+    where C: Send + 'static,
+          Codec: ConfigurationCodec<C>
+{
+    // The lock is held while saving, so concurrent reads never write the snapshot at the same time
+    let mut save = save.lock().unwrap();
+
+    let encoded = match snapshot.codec().encode(configuration) {
+        Ok(encoded) => encoded,
+        Err(e) => {
+            save.saved = None;
+            save.error = Some(FileConfigurationWriteError::Encode(e));
+            return;
+        }
+    };
+
+    // The snapshot is saved again when it was removed, even though the configuration is unchanged
+    if save.saved.as_ref() != Some(&encoded) || !snapshot.path().exists() {
+        if let Err(e) = snapshot.write_configuration(configuration).wait() {
+            save.saved = None;
+            save.error = Some(e);
+            return;
+        }
+
+        save.saved = Some(encoded);
+    }
+
+    save.error = record_read(snapshot.path()).err().map(FileConfigurationWriteError::Io);
+}
+
+/// Gets the path of the file recording when the configuration of the snapshot was last read.
+fn read_at_path(path: &Path) -> PathBuf {
+    let mut read_at_path = OsString::from(path.as_os_str());
+    read_at_path.push(".read");
+    PathBuf::from(read_at_path)
+}
+
+fn record_read(path: &Path) -> io::Result<()> {
+    let read_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_secs(0));
+    let read_at = read_at.as_secs() * 1000 + u64::from(read_at.subsec_nanos() / 1_000_000);
+
+    fs::write(read_at_path(path), read_at.to_string())
+}
+
+fn snapshot_age(path: &Path) -> Duration {
+    // The age is how long ago the configuration was last read, falling back to when the snapshot was saved
+    let read_at = fs::read_to_string(read_at_path(path))
+        .ok()
+        .and_then(|read_at| read_at.trim().parse().ok())
+        .map(|read_at| UNIX_EPOCH + Duration::from_millis(read_at));
+
+    let read_at = match read_at {
+        Some(read_at) => Some(read_at),
+        None => fs::metadata(path).and_then(|metadata| metadata.modified()).ok(),
+    };
+
+    // A snapshot read in the future, such as after the clock moved back, is treated as new
+    read_at.and_then(|read_at| read_at.elapsed().ok())
+        .unwrap_or_else(|| Duration::from_secs(0))
+}
+
+impl<R, Codec> ConfigurationReader for SnapshotConfigurationReader<R, Codec>
+    where R: ConfigurationReader,
+          Codec: ConfigurationCodec<R::Configuration> + Clone + Send + 'static
+{
+    type Configuration = LastKnownGood<R::Configuration>;
+    type Error = SnapshotConfigurationReadError<R::Error, Codec::Error>;
+    type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        let mut snapshot = self.snapshot.clone();
+        let save = self.save.clone();
+
+        self.reader
+            .read_configuration()
+            .then(move |result| match result {
+                Ok(configuration) => {
+                    save_snapshot(&save, &mut snapshot, &configuration);
+                    ok(LastKnownGood::fresh(configuration)).boxed()
+                }
+                Err(e) => {
+                    let age = snapshot_age(snapshot.path());
+
+                    snapshot.read_configuration()
+                        .map(move |configuration| LastKnownGood::stale(configuration, age))
+                        .map_err(move |snapshot_error| SnapshotConfigurationReadError::new(e, snapshot_error))
+                        .boxed()
+                }
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ConfigurationCodec, ConfigurationReader, ConfigurationWriter, FluentConfigurationReader};
+    use file::FileConfigurationWriteError;
+    use memory::MemoryConfigurationAccessor;
+    use snapshot::{LastKnownGood, SnapshotConfigurationReader};
+    use futures::Future;
+    use std::num::ParseIntError;
+    use std::time::Duration;
+    use tempdir::TempDir;
+
+    #[derive(Debug, Clone)]
+    struct TestCodec;
+
+    impl ConfigurationCodec<u32> for TestCodec {
+        type Error = ParseIntError;
+
+        fn decode(&self, bytes: &[u8]) -> Result<u32, Self::Error> {
+            String::from_utf8_lossy(bytes).parse()
+        }
+
+        fn encode(&self, configuration: &u32) -> Result<Vec<u8>, Self::Error> {
+            Ok(configuration.to_string().into_bytes())
+        }
+    }
+
+    #[test]
+    fn read_configuration_saves_snapshot() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let path = directory.path().join("snapshot");
+        let reader = SnapshotConfigurationReader::new(MemoryConfigurationAccessor::new(7), &path, TestCodec);
+
+        // Act
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration, LastKnownGood::fresh(7));
+        assert_eq!(TestCodec.decode(&::std::fs::read(&path).unwrap()), Ok(7));
+    }
+
+    #[test]
+    fn read_configuration_failed_read_returns_stale_snapshot() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let mut origin = MemoryConfigurationAccessor::new(Some(7));
+        let reader = SnapshotConfigurationReader::new(origin.clone().and_then(|value: Option<u32>| value.ok_or("unavailable")),
+                                                      directory.path().join("snapshot"),
+                                                      TestCodec);
+        reader.read_configuration().wait().unwrap();
+
+        // Act
+        origin.write_configuration(&None).wait().unwrap();
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert!(configuration.is_stale());
+        assert!(configuration.stale_age().is_some());
+        assert_eq!(configuration.into_configuration(), 7);
+    }
+
+    #[test]
+    fn read_configuration_without_snapshot_returns_both_errors() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let reader = SnapshotConfigurationReader::new(MemoryConfigurationAccessor::<u32>::empty(),
+                                                      directory.path().join("snapshot"),
+                                                      TestCodec);
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        assert!(error.snapshot_error().is_not_found());
+    }
+
+    #[test]
+    fn read_configuration_unchanged_configuration_restores_removed_snapshot() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let path = directory.path().join("snapshot");
+        let reader = SnapshotConfigurationReader::new(MemoryConfigurationAccessor::new(7), &path, TestCodec);
+        reader.read_configuration().wait().unwrap();
+        ::std::fs::remove_file(&path).unwrap();
+
+        // Act
+        reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(TestCodec.decode(&::std::fs::read(&path).unwrap()), Ok(7));
+    }
+
+    #[test]
+    fn read_configuration_stale_age_is_since_last_read() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let path = directory.path().join("snapshot");
+        let mut origin = MemoryConfigurationAccessor::new(Some(7));
+        let reader = SnapshotConfigurationReader::new(origin.clone().and_then(|value: Option<u32>| value.ok_or("unavailable")),
+                                                      &path,
+                                                      TestCodec);
+        reader.read_configuration().wait().unwrap();
+        let mut read_at_path = path.clone().into_os_string();
+        read_at_path.push(".read");
+        ::std::fs::write(&read_at_path, "0").unwrap();
+
+        // Act
+        reader.read_configuration().wait().unwrap();
+        origin.write_configuration(&None).wait().unwrap();
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert!(configuration.stale_age().unwrap() < Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn read_configuration_failed_save_keeps_save_error() {
+        // Arrange
+        let directory = TempDir::new("lz_configuration").unwrap();
+        let reader = SnapshotConfigurationReader::new(MemoryConfigurationAccessor::new(7),
+                                                      directory.path().join("missing").join("snapshot"),
+                                                      TestCodec);
+
+        // Act
+        let configuration = reader.read_configuration().wait().unwrap();
+
+        // Assert
+        assert_eq!(configuration, LastKnownGood::fresh(7));
+        match reader.take_save_error() {
+            Some(FileConfigurationWriteError::Io(_)) => {}
+            other => panic!("expected an io error, got {:?}", other),
+        }
+        assert!(reader.take_save_error().is_none());
+    }
+}
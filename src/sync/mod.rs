@@ -1,2 +1,5 @@
 mod concurrent_writer;
 pub use self::concurrent_writer::*;
+
+mod single_flight;
+pub use self::single_flight::*;
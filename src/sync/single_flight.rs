@@ -0,0 +1,182 @@
+use futures::{BoxFuture, Future};
+use futures::future::{Shared, SharedError};
+use futures::sync::oneshot;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+type InFlight<T, E> = Arc<Mutex<Option<Shared<BoxFuture<T, E>>>>>;
+
+fn lock<T, E>(in_flight: &InFlight<T, E>) -> MutexGuard<Option<Shared<BoxFuture<T, E>>>> {
+    // Nothing panics while the lock is held, should it happen the slot is still usable
+    in_flight.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Shares one in-flight future between everyone who asks for it while it is running.
+///
+/// Use this to coalesce concurrent reads of the same configuration, every caller receives the result or error
+/// of the same read. Once the future completes the next call starts a new one.
+pub struct SingleFlight<T, E> {
+    in_flight: InFlight<T, E>,
+}
+
+impl<T, E> SingleFlight<T, E> {
+    pub fn new() -> Self {
+        Self { in_flight: Arc::new(Mutex::new(None)) }
+    }
+
+    /// Gets whether there is a future in flight.
+    pub fn is_in_flight(&self) -> bool {
+        lock(&self.in_flight).is_some()
+    }
+}
+
+impl<T, E> SingleFlight<T, E>
+    where T: Clone + Send + Sync + 'static,
+          E: Send + Sync + 'static
+{
+    /// Gets the in-flight future, calling `start` to create it when there is no future in flight.
+    ///
+    /// `start` is called without holding the lock, callers arriving meanwhile share the future it creates.
+    pub fn run<F>(&self, start: F) -> BoxFuture<T, SharedError<E>>
+        where F: FnOnce() -> BoxFuture<T, E>
+    {
+        let (started, shared) = {
+            let mut in_flight = lock(&self.in_flight);
+
+            match *in_flight {
+                Some(ref shared) => (None, shared.clone()),
+                None => {
+                    // The slot holds a placeholder which waits for the future `start` creates
+                    let (started, future) = oneshot::channel::<BoxFuture<T, E>>();
+                    let completed = self.in_flight.clone();
+                    let shared = future.then(|future| match future {
+                            Ok(future) => future,
+                            Err(_) => panic!("the future in flight panicked while it was being created"),
+                        })
+                        .then(move |result| {
+                            lock(&completed).take();
+                            result
+                        })
+                        .boxed()
+                        .shared();
+
+                    *in_flight = Some(shared.clone());
+                    (Some(started), shared)
+                }
+            }
+        };
+
+        if let Some(started) = started {
+            match panic::catch_unwind(AssertUnwindSafe(start)) {
+                Ok(future) => {
+                    let _ = started.send(future);
+                }
+                Err(panic) => {
+                    lock(&self.in_flight).take();
+                    panic::resume_unwind(panic);
+                }
+            }
+        }
+
+        shared.map(|item| (*item).clone()).boxed()
+    }
+}
+
+impl<T, E> Clone for SingleFlight<T, E> {
+    fn clone(&self) -> Self {
+        // Clone should share the same in-flight future
+        Self { in_flight: self.in_flight.clone() }
+    }
+}
+
+impl<T, E> Default for SingleFlight<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, E> Debug for SingleFlight<T, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("SingleFlight")
+            .field("in_flight", &lock(&self.in_flight).is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sync::SingleFlight;
+    use futures::Future;
+    use futures::future::{err, ok};
+    use futures::sync::oneshot;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn run_concurrent_callers_share_one_future() {
+        // Arrange
+        let flight = SingleFlight::<u32, String>::new();
+        let started = Arc::new(AtomicUsize::new(0));
+        let (complete, completed) = oneshot::channel::<u32>();
+        let mut completed = Some(completed);
+
+        // Act
+        let runs: Vec<_> = (0..3)
+            .map(|_| {
+                flight.run(|| {
+                    started.fetch_add(1, Ordering::SeqCst);
+                    completed.take().unwrap().map_err(|_| "cancelled".to_owned()).boxed()
+                })
+            })
+            .collect();
+        complete.send(5).unwrap();
+        let results: Vec<_> = runs.into_iter().map(|run| run.wait().unwrap()).collect();
+
+        // Assert
+        assert_eq!(started.load(Ordering::SeqCst), 1);
+        assert_eq!(results, vec![5, 5, 5]);
+        assert!(!flight.is_in_flight());
+    }
+
+    #[test]
+    fn run_after_completion_starts_new_future() {
+        // Arrange
+        let flight = SingleFlight::<u32, String>::new();
+        let first = flight.run(|| err("unavailable".to_owned()).boxed()).wait();
+
+        // Act
+        let second = flight.run(|| ok(7).boxed()).wait();
+
+        // Assert
+        assert_eq!(*first.unwrap_err(), "unavailable");
+        assert_eq!(second.unwrap(), 7);
+    }
+
+    #[test]
+    fn run_start_runs_without_lock() {
+        // Arrange
+        let flight = SingleFlight::<bool, String>::new();
+
+        // Act
+        let result = flight.run(|| ok(flight.is_in_flight()).boxed()).wait();
+
+        // Assert
+        assert_eq!(result.unwrap(), true);
+    }
+
+    #[test]
+    fn run_after_panicking_start_starts_new_future() {
+        // Arrange
+        let flight = SingleFlight::<u32, String>::new();
+        let panicked = panic::catch_unwind(AssertUnwindSafe(|| flight.run(|| panic!("start failed"))));
+
+        // Act
+        let result = flight.run(|| ok(7).boxed()).wait();
+
+        // Assert
+        assert!(panicked.is_err());
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,427 @@
+use super::ConfigurationValueError;
+use serde::{Serialize, Serializer};
+use serde::de::{Deserialize, DeserializeOwned, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::collections::BTreeMap;
+use std::fmt::{Formatter, Result as FmtResult};
+
+/// An untyped configuration, as produced by sources which only know about keys and text values such as
+/// environment variables or command-line arguments.
+///
+/// A `ConfigurationValue` can be converted into a typed configuration with `deserialize_into`, text values
+/// are parsed into whichever type is requested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigurationValue {
+    String(String),
+    Array(Vec<ConfigurationValue>),
+    Table(BTreeMap<String, ConfigurationValue>),
+}
+
+impl Default for ConfigurationValue {
+    fn default() -> Self {
+        ConfigurationValue::Table(BTreeMap::new())
+    }
+}
+
+impl ConfigurationValue {
+    /// Creates a new empty `ConfigurationValue::Table`.
+    pub fn table() -> Self {
+        Self::default()
+    }
+
+    /// Gets the text of this value if it is a `ConfigurationValue::String`.
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            ConfigurationValue::String(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Gets the elements of this value if it is a `ConfigurationValue::Array`.
+    pub fn as_array(&self) -> Option<&Vec<ConfigurationValue>> {
+        match *self {
+            ConfigurationValue::Array(ref values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Gets the entries of this value if it is a `ConfigurationValue::Table`.
+    pub fn as_table(&self) -> Option<&BTreeMap<String, ConfigurationValue>> {
+        match *self {
+            ConfigurationValue::Table(ref table) => Some(table),
+            _ => None,
+        }
+    }
+
+    /// Gets the value at the specified path of nested keys.
+    pub fn get<K: AsRef<str>>(&self, path: &[K]) -> Option<&ConfigurationValue> {
+        path.iter().fold(Some(self), |value, key| {
+            value.and_then(|value| value.as_table()).and_then(|table| table.get(key.as_ref()))
+        })
+    }
+
+    /// Inserts a value at the specified path of nested keys, creating any intermediate tables.
+    ///
+    /// Any value which is not a table and lies on the path is replaced by a table.
+    pub fn insert<K: AsRef<str>>(&mut self, path: &[K], value: ConfigurationValue) {
+        let (last, parents) = match path.split_last() {
+            Some(split) => split,
+            None => {
+                *self = value;
+                return;
+            }
+        };
+
+        let mut current = self;
+        for key in parents {
+            current = current.table_mut()
+                .entry(key.as_ref().to_owned())
+                .or_insert_with(ConfigurationValue::table);
+        }
+
+        current.table_mut().insert(last.as_ref().to_owned(), value);
+    }
+
+    /// Inserts a value at the specified path of nested keys, creating any intermediate tables.
+    ///
+    /// Unlike `insert` a value is never replaced by a table or a table by a value, an error is returned when
+    /// a value which is not a table lies on the path or when the value and an existing value at the path are
+    /// not both tables or both not tables.
+    pub fn try_insert<K: AsRef<str>>(&mut self, path: &[K], value: ConfigurationValue) -> Result<(), ConfigurationValueError> {
+        let path_to = |index: usize| path[..index + 1].iter().map(|key| key.as_ref().to_owned()).collect::<Vec<_>>();
+
+        for index in 0..path.len() {
+            let existing = match self.get(&path[..index + 1]) {
+                Some(existing) => existing,
+                None => break,
+            };
+
+            let is_last = index + 1 == path.len();
+            let conflicts = if is_last {
+                existing.as_table().is_some() != value.as_table().is_some()
+            } else {
+                existing.as_table().is_none()
+            };
+
+            if conflicts {
+                return Err(ConfigurationValueError::invalid(path_to(index), "a value and a table cannot share a key"));
+            }
+        }
+
+        self.insert(path, value);
+        Ok(())
+    }
+
+    /// Gets a mutable reference to the value at the specified path of nested keys.
+    pub fn get_mut<K: AsRef<str>>(&mut self, path: &[K]) -> Option<&mut ConfigurationValue> {
+        let mut current = self;
+        for key in path {
+            current = match *current {
+                ConfigurationValue::Table(ref mut table) => table.get_mut(key.as_ref())?,
+                _ => return None,
+            };
+        }
+
+        Some(current)
+    }
+
+    /// Appends a value at the specified path of nested keys, an existing value at the path is converted into
+    /// a `ConfigurationValue::Array` which the new value is added to.
+    pub fn append<K: AsRef<str>>(&mut self, path: &[K], value: ConfigurationValue) {
+        if let Some(existing) = self.get_mut(path) {
+            match *existing {
+                ConfigurationValue::Array(ref mut values) => values.push(value),
+                ref mut other => {
+                    let previous = ::std::mem::replace(other, ConfigurationValue::Array(Vec::new()));
+                    *other = ConfigurationValue::Array(vec![previous, value]);
+                }
+            }
+
+            return;
+        }
+
+        self.insert(path, value);
+    }
+
+    /// Merges another value over this value, the entries of tables are merged recursively and any other
+    /// value is replaced.
+    pub fn merge(&mut self, other: ConfigurationValue) {
+        match other {
+            ConfigurationValue::Table(entries) if self.as_table().is_some() => {
+                let table = self.table_mut();
+                for (key, value) in entries {
+                    match table.get_mut(&key) {
+                        Some(existing) => existing.merge(value),
+                        None => {
+                            table.insert(key, value);
+                        }
+                    }
+                }
+            }
+            other => *self = other,
+        }
+    }
+
+    fn table_mut(&mut self) -> &mut BTreeMap<String, ConfigurationValue> {
+        if self.as_table().is_none() {
+            *self = ConfigurationValue::table();
+        }
+
+        match *self {
+            ConfigurationValue::Table(ref mut table) => table,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Converts this value into a typed configuration.
+    ///
+    /// Text values are parsed into numbers and booleans where requested, and a sequence may be read from
+    /// a comma separated string.
+    pub fn deserialize_into<C: DeserializeOwned>(self) -> Result<C, ConfigurationValueError> {
+        C::deserialize(self)
+    }
+}
+
+impl From<String> for ConfigurationValue {
+    fn from(value: String) -> Self {
+        ConfigurationValue::String(value)
+    }
+}
+
+impl<'a> From<&'a str> for ConfigurationValue {
+    fn from(value: &'a str) -> Self {
+        ConfigurationValue::String(value.to_owned())
+    }
+}
+
+impl From<Vec<ConfigurationValue>> for ConfigurationValue {
+    fn from(value: Vec<ConfigurationValue>) -> Self {
+        ConfigurationValue::Array(value)
+    }
+}
+
+impl From<BTreeMap<String, ConfigurationValue>> for ConfigurationValue {
+    fn from(value: BTreeMap<String, ConfigurationValue>) -> Self {
+        ConfigurationValue::Table(value)
+    }
+}
+
+struct ConfigurationValueVisitor;
+
+impl<'de> Visitor<'de> for ConfigurationValueVisitor {
+    type Value = ConfigurationValue;
+
+    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "a configuration value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(ConfigurationValue::String(value.to_string()))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(ConfigurationValue::String(value.to_string()))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(ConfigurationValue::String(value.to_string()))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(ConfigurationValue::String(value.to_string()))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(ConfigurationValue::String(value.to_owned()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+        Ok(ConfigurationValue::String(value))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(ConfigurationValue::String(String::new()))
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+
+        Ok(ConfigurationValue::Array(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut table = BTreeMap::new();
+        while let Some((key, value)) = map.next_entry()? {
+            table.insert(key, value);
+        }
+
+        Ok(ConfigurationValue::Table(table))
+    }
+}
+
+impl<'de> Deserialize<'de> for ConfigurationValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ConfigurationValueVisitor)
+    }
+}
+
+impl Serialize for ConfigurationValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            ConfigurationValue::String(ref value) => serializer.serialize_str(value),
+            ConfigurationValue::Array(ref values) => serializer.collect_seq(values),
+            ConfigurationValue::Table(ref table) => serializer.collect_map(table),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use value::{ConfigurationValue, ConfigurationValueError};
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct ServerConfiguration {
+        host: String,
+        port: u16,
+        verbose: Option<bool>,
+        tags: Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct TestConfiguration {
+        server: ServerConfiguration,
+    }
+
+    fn server_value(port: &str) -> ConfigurationValue {
+        let mut value = ConfigurationValue::table();
+        value.insert(&["server", "host"], "localhost".into());
+        value.insert(&["server", "port"], port.into());
+        value.insert(&["server", "tags"], "a, b".into());
+        value
+    }
+
+    #[test]
+    fn insert_creates_nested_tables() {
+        // Arrange
+        let mut value = ConfigurationValue::table();
+
+        // Act
+        value.insert(&["a", "b"], "c".into());
+
+        // Assert
+        assert_eq!(value.get(&["a", "b"]), Some(&ConfigurationValue::from("c")));
+    }
+
+    #[test]
+    fn try_insert_value_over_table_returns_error() {
+        // Arrange
+        let mut value = ConfigurationValue::table();
+        value.insert(&["a", "b"], "2".into());
+
+        // Act
+        let error = value.try_insert(&["a"], "1".into()).unwrap_err();
+
+        // Assert
+        assert_eq!(error.path(), &["a".to_owned()]);
+        assert_eq!(value.get(&["a", "b"]), Some(&ConfigurationValue::from("2")));
+    }
+
+    #[test]
+    fn try_insert_below_value_returns_error() {
+        // Arrange
+        let mut value = ConfigurationValue::table();
+        value.insert(&["a"], "1".into());
+
+        // Act
+        let error = value.try_insert(&["a", "b"], "2".into()).unwrap_err();
+
+        // Assert
+        assert_eq!(error.path(), &["a".to_owned()]);
+        assert_eq!(value.get(&["a"]), Some(&ConfigurationValue::from("1")));
+    }
+
+    #[test]
+    fn merge_merges_nested_tables() {
+        // Arrange
+        let mut value = server_value("8080");
+
+        let mut other = ConfigurationValue::table();
+        other.insert(&["server", "port"], "9090".into());
+        other.insert(&["server", "tags"], ConfigurationValue::Array(vec!["c".into()]));
+
+        // Act
+        value.merge(other);
+
+        // Assert
+        assert_eq!(value.get(&["server", "host"]), Some(&ConfigurationValue::from("localhost")));
+        assert_eq!(value.get(&["server", "port"]), Some(&ConfigurationValue::from("9090")));
+        assert_eq!(value.get(&["server", "tags"]), Some(&ConfigurationValue::Array(vec!["c".into()])));
+    }
+
+    #[test]
+    fn deserialize_into_parses_text_values() {
+        // Arrange
+        let value = server_value("8080");
+
+        // Act
+        let configuration: TestConfiguration = value.deserialize_into().unwrap();
+
+        // Assert
+        assert_eq!(configuration,
+                   TestConfiguration {
+                       server: ServerConfiguration {
+                           host: "localhost".to_owned(),
+                           port: 8080,
+                           verbose: None,
+                           tags: vec!["a".to_owned(), "b".to_owned()],
+                       },
+                   });
+    }
+
+    #[test]
+    fn deserialize_into_invalid_value_returns_path() {
+        // Arrange
+        let value = server_value("not a port");
+
+        // Act
+        let error = value.deserialize_into::<TestConfiguration>().unwrap_err();
+
+        // Assert
+        assert_eq!(error.path(), &["server".to_owned(), "port".to_owned()]);
+        assert!(!error.is_missing());
+    }
+
+    #[test]
+    fn deserialize_into_missing_value_returns_path() {
+        // Arrange
+        let mut value = ConfigurationValue::table();
+        value.insert(&["server", "port"], "8080".into());
+
+        // Act
+        let error = value.deserialize_into::<TestConfiguration>().unwrap_err();
+
+        // Assert
+        assert_eq!(error, ConfigurationValueError::missing(vec!["server".to_owned(), "host".to_owned()]));
+    }
+
+    #[test]
+    fn deserialize_into_configuration_value_returns_same_value() {
+        // Arrange
+        let mut table = BTreeMap::new();
+        table.insert("key".to_owned(), ConfigurationValue::Array(vec!["value".into()]));
+        let value = ConfigurationValue::Table(table);
+
+        // Act
+        let result: ConfigurationValue = value.clone().deserialize_into().unwrap();
+
+        // Assert
+        assert_eq!(result, value);
+    }
+}
@@ -0,0 +1,354 @@
+use super::{ConfigurationValue, ConfigurationValueError};
+use serde::de::{DeserializeSeed, Deserializer, EnumAccess, Error as DeError, IntoDeserializer, MapAccess,
+                SeqAccess, Unexpected, VariantAccess, Visitor};
+use std::collections::btree_map::IntoIter as TableIntoIter;
+use std::vec::IntoIter as ArrayIntoIter;
+
+impl ConfigurationValue {
+    fn unexpected(&self) -> Unexpected<'_> {
+        match *self {
+            ConfigurationValue::String(ref value) => Unexpected::Str(value),
+            ConfigurationValue::Array(_) => Unexpected::Seq,
+            ConfigurationValue::Table(_) => Unexpected::Map,
+        }
+    }
+
+    fn invalid_type<'de, V: Visitor<'de>>(&self, visitor: &V) -> ConfigurationValueError {
+        ConfigurationValueError::invalid_type(self.unexpected(), visitor)
+    }
+
+    fn into_array(self) -> Option<Vec<ConfigurationValue>> {
+        match self {
+            ConfigurationValue::Array(values) => Some(values),
+            ConfigurationValue::String(value) => {
+                // Sources such as environment variables can only express a list as delimited text
+                let values = value.split(',')
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(ConfigurationValue::from)
+                    .collect();
+
+                Some(values)
+            }
+            ConfigurationValue::Table(table) => {
+                // Sources which only have keys may express a list as a table of indices
+                let mut indexed = Vec::with_capacity(table.len());
+                for (key, value) in table {
+                    match key.parse::<usize>() {
+                        Ok(index) => indexed.push((index, value)),
+                        Err(_) => return None,
+                    }
+                }
+
+                indexed.sort_by_key(|&(index, _)| index);
+                Some(indexed.into_iter().map(|(_, value)| value).collect())
+            }
+        }
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self {
+                    ConfigurationValue::String(value) => {
+                        match value.trim().parse() {
+                            Ok(parsed) => visitor.$visit(parsed),
+                            Err(err) => Err(ConfigurationValueError::invalid(Vec::new(), format!("'{}' {}", value, err))),
+                        }
+                    }
+                    other => Err(other.invalid_type(&visitor)),
+                }
+            }
+        )*
+    }
+}
+
+impl<'de> Deserializer<'de> for ConfigurationValue {
+    type Error = ConfigurationValueError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            ConfigurationValue::String(value) => visitor.visit_string(value),
+            ConfigurationValue::Array(values) => visitor.visit_seq(ArrayAccess::new(values)),
+            ConfigurationValue::Table(table) => visitor.visit_map(TableAccess::new(table.into_iter())),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let parsed = self.as_str().and_then(parse_bool);
+        match parsed {
+            Some(value) => visitor.visit_bool(value),
+            None => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            ConfigurationValue::String(value) => visitor.visit_string(value),
+            other => Err(other.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            ConfigurationValue::String(value) => visitor.visit_byte_buf(value.into_bytes()),
+            other => other.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            ConfigurationValue::String(ref value) if value.is_empty() => visitor.visit_unit(),
+            other => Err(other.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self,
+                                                _name: &'static str,
+                                                visitor: V)
+                                                -> Result<V::Value, Self::Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self,
+                                                   _name: &'static str,
+                                                   visitor: V)
+                                                   -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let unexpected = self.invalid_type(&visitor);
+        match self.into_array() {
+            Some(values) => visitor.visit_seq(ArrayAccess::new(values)),
+            None => Err(unexpected),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self,
+                                                 _name: &'static str,
+                                                 _len: usize,
+                                                 visitor: V)
+                                                 -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            ConfigurationValue::Table(table) => visitor.visit_map(TableAccess::new(table.into_iter())),
+            other => Err(other.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self,
+                                           _name: &'static str,
+                                           _fields: &'static [&'static str],
+                                           visitor: V)
+                                           -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self,
+                                         _name: &'static str,
+                                         _variants: &'static [&'static str],
+                                         visitor: V)
+                                         -> Result<V::Value, Self::Error> {
+        match self {
+            ConfigurationValue::String(variant) => {
+                visitor.visit_enum(VariantDeserializer {
+                    variant: variant,
+                    value: None,
+                })
+            }
+            ConfigurationValue::Table(table) => {
+                if table.len() != 1 {
+                    return Err(ConfigurationValueError::invalid_length(table.len(), &"a table with a single key"));
+                }
+
+                let (variant, value) = table.into_iter().next().unwrap();
+                visitor.visit_enum(VariantDeserializer {
+                    variant: variant,
+                    value: Some(value),
+                })
+            }
+            other => Err(other.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+}
+
+struct ArrayAccess {
+    values: ArrayIntoIter<ConfigurationValue>,
+    index: usize,
+}
+
+impl ArrayAccess {
+    fn new(values: Vec<ConfigurationValue>) -> Self {
+        Self {
+            values: values.into_iter(),
+            index: 0,
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for ArrayAccess {
+    type Error = ConfigurationValueError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> {
+        match self.values.next() {
+            Some(value) => {
+                let index = self.index;
+                self.index += 1;
+
+                seed.deserialize(value)
+                    .map(Some)
+                    .map_err(|err| err.with_parent(&index.to_string()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+struct TableAccess {
+    entries: TableIntoIter<String, ConfigurationValue>,
+    current: Option<(String, ConfigurationValue)>,
+}
+
+impl TableAccess {
+    fn new(entries: TableIntoIter<String, ConfigurationValue>) -> Self {
+        Self {
+            entries: entries,
+            current: None,
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for TableAccess {
+    type Error = ConfigurationValueError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                let deserialized = seed.deserialize(key.clone().into_deserializer())
+                    .map_err(|err: ConfigurationValueError| err.with_parent(&key))?;
+                self.current = Some((key, value));
+
+                Ok(Some(deserialized))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        let (key, value) = self.current.take().expect("next_value_seed called before next_key_seed");
+        seed.deserialize(value).map_err(|err| err.with_parent(&key))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct VariantDeserializer {
+    variant: String,
+    value: Option<ConfigurationValue>,
+}
+
+impl<'de> EnumAccess<'de> for VariantDeserializer {
+    type Error = ConfigurationValueError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(self.variant.clone().into_deserializer())?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer {
+    type Error = ConfigurationValueError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.value {
+            None => Ok(()),
+            Some(value) => Deserializer::deserialize_ignored_any(value, ::serde::de::IgnoredAny).map(drop),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Self::Error> {
+        let variant = self.variant;
+        match self.value {
+            Some(value) => seed.deserialize(value).map_err(|err| err.with_parent(&variant)),
+            None => Err(ConfigurationValueError::invalid_type(Unexpected::UnitVariant, &"a newtype variant")),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        let variant = self.variant;
+        match self.value {
+            Some(value) => value.deserialize_seq(visitor).map_err(|err| err.with_parent(&variant)),
+            None => Err(ConfigurationValueError::invalid_type(Unexpected::UnitVariant, &"a tuple variant")),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self,
+                                       _fields: &'static [&'static str],
+                                       visitor: V)
+                                       -> Result<V::Value, Self::Error> {
+        let variant = self.variant;
+        match self.value {
+            Some(value) => value.deserialize_map(visitor).map_err(|err| err.with_parent(&variant)),
+            None => Err(ConfigurationValueError::invalid_type(Unexpected::UnitVariant, &"a struct variant")),
+        }
+    }
+}
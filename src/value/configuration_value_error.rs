@@ -0,0 +1,86 @@
+use serde::de::Error as DeError;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ConfigurationValueErrorKind {
+    Missing,
+    Invalid(String),
+}
+
+/// The error when a `ConfigurationValue` could not be converted into a typed configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigurationValueError {
+    path: Vec<String>,
+    kind: ConfigurationValueErrorKind,
+}
+
+impl ConfigurationValueError {
+    /// Creates a new `ConfigurationValueError` for a required value which was not present.
+    pub fn missing(path: Vec<String>) -> Self {
+        Self {
+            path: path,
+            kind: ConfigurationValueErrorKind::Missing,
+        }
+    }
+
+    /// Creates a new `ConfigurationValueError` for a value which could not be parsed.
+    pub fn invalid<M: Into<String>>(path: Vec<String>, message: M) -> Self {
+        Self {
+            path: path,
+            kind: ConfigurationValueErrorKind::Invalid(message.into()),
+        }
+    }
+
+    /// Gets the path of nested keys to the value which caused the error.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// Returns whether the error was caused by a required value not being present.
+    pub fn is_missing(&self) -> bool {
+        self.kind == ConfigurationValueErrorKind::Missing
+    }
+
+    /// Gets the reason a value could not be parsed, this is `None` if the value was missing.
+    pub fn message(&self) -> Option<&str> {
+        match self.kind {
+            ConfigurationValueErrorKind::Missing => None,
+            ConfigurationValueErrorKind::Invalid(ref message) => Some(message),
+        }
+    }
+
+    pub(super) fn with_parent(mut self, key: &str) -> Self {
+        self.path.insert(0, key.to_owned());
+        self
+    }
+}
+
+impl Display for ConfigurationValueError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let path = self.path.join(".");
+        match self.kind {
+            ConfigurationValueErrorKind::Missing => write!(f, "missing value '{}'", path),
+            ConfigurationValueErrorKind::Invalid(ref message) => write!(f, "invalid value '{}': {}", path, message),
+        }
+    }
+}
+
+impl Error for ConfigurationValueError {
+    fn description(&self) -> &str {
+        match self.kind {
+            ConfigurationValueErrorKind::Missing => "missing value",
+            ConfigurationValueErrorKind::Invalid(_) => "invalid value",
+        }
+    }
+}
+
+impl DeError for ConfigurationValueError {
+    fn custom<T: Display>(message: T) -> Self {
+        ConfigurationValueError::invalid(Vec::new(), message.to_string())
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        ConfigurationValueError::missing(vec![field.to_owned()])
+    }
+}
@@ -0,0 +1,7 @@
+mod configuration_value;
+pub use self::configuration_value::*;
+
+mod configuration_value_error;
+pub use self::configuration_value_error::*;
+
+mod configuration_value_deserializer;
@@ -0,0 +1,42 @@
+/// Creates a `ZipConfigurationReader` which reads two or more readers concurrently.
+///
+/// Without a closure the configuration is nested pairs, `zip_configuration!(a, b, c)` reads
+/// `(A, (B, C))`. With a closure after `=>` the configurations are passed to it as separate arguments:
+///
+/// ```ignore
+/// let settings = zip_configuration!(database, flags, file => |database, flags, file| Settings {
+///     database: database,
+///     flags: flags,
+///     file: file,
+/// });
+/// ```
+///
+/// The error is nested in the same way, `Either::Left` when the first reader fails and `Either::Right` with the
+/// error of the remaining readers otherwise.
+#[macro_export]
+macro_rules! zip_configuration {
+    ($first:expr, $second:expr) => {
+        $crate::zip::ZipConfigurationReader::new($first, $second)
+    };
+    ($first:expr, $($rest:expr),+) => {
+        $crate::zip::ZipConfigurationReader::new($first, zip_configuration!($($rest),+))
+    };
+    ($($reader:expr),+ => |$($configuration:ident),+| $body:expr) => {
+        $crate::FluentConfigurationReader::map(zip_configuration!($($reader),+),
+                                               move |__zip_configuration_pattern!($($configuration),+)| $body)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __zip_configuration_pattern {
+    ($first:ident, $second:ident) => {
+        ($first, $second)
+    };
+    ($first:ident, $($rest:ident),+) => {
+        ($first, __zip_configuration_pattern!($($rest),+))
+    };
+}
+
+mod zip_configuration_reader;
+pub use self::zip_configuration_reader::*;
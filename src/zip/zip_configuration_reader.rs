@@ -0,0 +1,132 @@
+use ConfigurationReader;
+use futures::{BoxFuture, Future};
+use either::Either;
+
+/// A `ConfigurationReader` which reads two readers concurrently, returning both configurations.
+///
+/// Unlike `FallbackConfigurationReader` the readers may read different configuration types. The error is
+/// `Either::Left` when the first reader fails and `Either::Right` when the second reader fails, more readers
+/// can be read with `zip_configuration!`.
+#[derive(Debug, Clone)]
+pub struct ZipConfigurationReader<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> ZipConfigurationReader<A, B>
+    where A: ConfigurationReader,
+          B: ConfigurationReader
+{
+    pub fn new(first: A, second: B) -> Self {
+        Self {
+            first: first,
+            second: second,
+        }
+    }
+}
+
+impl<A, B> ZipConfigurationReader<A, B> {
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A, B> ConfigurationReader for ZipConfigurationReader<A, B>
+    where A: ConfigurationReader,
+          B: ConfigurationReader
+{
+    type Configuration = (A::Configuration, B::Configuration);
+    type Error = Either<A::Error, B::Error>;
+    type ReadResult = BoxFuture<Self::Configuration, Self::Error>;
+
+    fn read_configuration(&self) -> Self::ReadResult {
+        let first = self.first.read_configuration().map_err(Either::Left);
+        let second = self.second.read_configuration().map_err(Either::Right);
+
+        first.join(second).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ConfigurationReader;
+    use zip::ZipConfigurationReader;
+    use memory::{MemoryConfigurationAccessor, MemoryConfigurationReadError};
+    use futures::Future;
+    use either::Either;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Settings {
+        name: String,
+        port: u16,
+        verbose: bool,
+    }
+
+    #[test]
+    fn read_configuration_returns_both_configurations() {
+        // Arrange
+        let reader = ZipConfigurationReader::new(MemoryConfigurationAccessor::new("app".to_owned()),
+                                                 MemoryConfigurationAccessor::new(8080u16));
+
+        // Act
+        let configuration = reader.read_configuration().wait();
+
+        // Assert
+        assert_eq!(configuration, Ok(("app".to_owned(), 8080)));
+    }
+
+    #[test]
+    fn read_configuration_failed_second_returns_right() {
+        // Arrange
+        let reader = ZipConfigurationReader::new(MemoryConfigurationAccessor::new("app".to_owned()),
+                                                 MemoryConfigurationAccessor::<u16>::empty());
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        assert_eq!(error, Either::Right(MemoryConfigurationReadError::NoConfiguration));
+    }
+
+    #[test]
+    fn zip_configuration_builds_configuration() {
+        // Arrange
+        let reader = zip_configuration!(MemoryConfigurationAccessor::new("app".to_owned()),
+                                        MemoryConfigurationAccessor::new(8080u16),
+                                        MemoryConfigurationAccessor::new(true)
+                                        => |name, port, verbose| Settings {
+                                            name: name,
+                                            port: port,
+                                            verbose: verbose,
+                                        });
+
+        // Act
+        let configuration = reader.read_configuration().wait();
+
+        // Assert
+        assert_eq!(configuration,
+                   Ok(Settings {
+                       name: "app".to_owned(),
+                       port: 8080,
+                       verbose: true,
+                   }));
+    }
+
+    #[test]
+    fn zip_configuration_failed_third_returns_nested_error() {
+        // Arrange
+        let reader = zip_configuration!(MemoryConfigurationAccessor::new("app".to_owned()),
+                                        MemoryConfigurationAccessor::new(8080u16),
+                                        MemoryConfigurationAccessor::<bool>::empty());
+
+        // Act
+        let error = reader.read_configuration().wait().unwrap_err();
+
+        // Assert
+        assert_eq!(error, Either::Right(Either::Right(MemoryConfigurationReadError::NoConfiguration)));
+    }
+}
@@ -0,0 +1,90 @@
+extern crate futures;
+extern crate lz_configuration;
+#[macro_use]
+extern crate serde_derive;
+
+use futures::Future;
+use lz_configuration::ConfigurationReader;
+use lz_configuration::memory::MemoryConfigurationAccessor;
+use lz_configuration::merge::{Merge, MergeConfigurationReader, MissingFieldsError, PartialConfiguration};
+use lz_configuration::value::ConfigurationValue;
+
+#[derive(Debug, Clone, PartialEq, Merge, PartialConfiguration)]
+#[partial(derive(Debug, Clone, PartialEq, Deserialize))]
+struct ServerConfiguration {
+    host: String,
+    #[serde(rename = "listen_port")]
+    port: u16,
+    #[partial(default)]
+    workers: u32,
+    banner: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, PartialConfiguration)]
+#[partial(derive(Debug, Clone, PartialEq, Deserialize))]
+struct AppConfiguration {
+    name: String,
+    #[partial(nested)]
+    server: ServerConfiguration,
+}
+
+fn server(host: &str, port: u16) -> ServerConfiguration {
+    ServerConfiguration {
+        host: host.to_owned(),
+        port: port,
+        workers: 4,
+        banner: None,
+    }
+}
+
+#[test]
+fn derive_merge_merges_fields() {
+    // Arrange
+    let mut configuration = server("localhost", 8080);
+
+    // Act
+    configuration.merge(server("example.com", 9090));
+
+    // Assert
+    assert_eq!(configuration, server("example.com", 9090));
+}
+
+#[test]
+fn finalize_reports_every_missing_field() {
+    // Arrange
+    let mut partial = PartialAppConfiguration::default();
+    partial.server.port = Some(8080);
+
+    // Act
+    let error = partial.finalize().unwrap_err();
+
+    // Assert
+    assert_eq!(error,
+               MissingFieldsError::new(vec!["name".to_owned(), "server.host".to_owned()]));
+}
+
+#[test]
+fn merge_reader_combines_partial_layers() {
+    // Arrange
+    let defaults = PartialAppConfiguration::from(AppConfiguration {
+        name: "app".to_owned(),
+        server: server("localhost", 8080),
+    });
+
+    let mut user_file = ConfigurationValue::table();
+    user_file.insert(&["server", "listen_port"], "9090".into());
+    let user_file: PartialAppConfiguration = user_file.deserialize_into().unwrap();
+
+    let reader = MergeConfigurationReader::new(MemoryConfigurationAccessor::new(defaults),
+                                               MemoryConfigurationAccessor::new(user_file));
+
+    // Act
+    let configuration = reader.read_configuration().wait().unwrap().finalize().unwrap();
+
+    // Assert
+    assert_eq!(configuration,
+               AppConfiguration {
+                   name: "app".to_owned(),
+                   server: server("localhost", 9090),
+               });
+}